@@ -0,0 +1,84 @@
+use super::client::ApiClient;
+use super::error::ApiError;
+use super::types::{Chore, ChoreHistory, CreateChoreRequest, UpdateChoreRequest};
+use std::future::Future;
+
+/// The chore operations the app needs from a Donetick server.
+///
+/// `ApiClient` talks to a real server; tests substitute an in-memory fake so
+/// the event handlers can run without one. Futures are `Send` so calls can be
+/// moved onto spawned tasks.
+pub trait ChoreApi: Clone + Send + Sync + 'static {
+    fn list_chores(&self) -> impl Future<Output = Result<Vec<Chore>, ApiError>> + Send;
+
+    fn chore_history(
+        &self,
+        id: i64,
+    ) -> impl Future<Output = Result<Vec<ChoreHistory>, ApiError>> + Send;
+
+    fn create_chore(
+        &self,
+        request: CreateChoreRequest,
+    ) -> impl Future<Output = Result<Chore, ApiError>> + Send;
+
+    fn update_chore(
+        &self,
+        id: i64,
+        request: UpdateChoreRequest,
+    ) -> impl Future<Output = Result<Chore, ApiError>> + Send;
+
+    fn complete_chore(&self, id: i64) -> impl Future<Output = Result<(), ApiError>> + Send;
+
+    fn delete_chore(&self, id: i64) -> impl Future<Output = Result<(), ApiError>> + Send;
+
+    /// Archives (deactivates) a chore, or restores it when `active` is true.
+    fn set_chore_active(
+        &self,
+        id: i64,
+        active: bool,
+    ) -> impl Future<Output = Result<(), ApiError>> + Send;
+}
+
+impl ChoreApi for ApiClient {
+    fn list_chores(&self) -> impl Future<Output = Result<Vec<Chore>, ApiError>> + Send {
+        ApiClient::list_chores(self)
+    }
+
+    fn chore_history(
+        &self,
+        id: i64,
+    ) -> impl Future<Output = Result<Vec<ChoreHistory>, ApiError>> + Send {
+        ApiClient::chore_history(self, id)
+    }
+
+    fn create_chore(
+        &self,
+        request: CreateChoreRequest,
+    ) -> impl Future<Output = Result<Chore, ApiError>> + Send {
+        ApiClient::create_chore(self, request)
+    }
+
+    fn update_chore(
+        &self,
+        id: i64,
+        request: UpdateChoreRequest,
+    ) -> impl Future<Output = Result<Chore, ApiError>> + Send {
+        ApiClient::update_chore(self, id, request)
+    }
+
+    fn complete_chore(&self, id: i64) -> impl Future<Output = Result<(), ApiError>> + Send {
+        ApiClient::complete_chore(self, id)
+    }
+
+    fn delete_chore(&self, id: i64) -> impl Future<Output = Result<(), ApiError>> + Send {
+        ApiClient::delete_chore(self, id)
+    }
+
+    fn set_chore_active(
+        &self,
+        id: i64,
+        active: bool,
+    ) -> impl Future<Output = Result<(), ApiError>> + Send {
+        ApiClient::set_chore_active(self, id, active)
+    }
+}
@@ -1,16 +1,32 @@
 use super::error::ApiError;
-use super::types::{Chore, ChoreListResponse, CreateChoreRequest};
+use super::retry::{self, ClientSettings, RetryPolicy};
+use super::types::{
+    Chore, ChoreHistory, ChoreHistoryResponse, ChoreListResponse, CreateChoreRequest,
+    UpdateChoreRequest,
+};
 use reqwest::header::{HeaderMap, HeaderValue};
-use std::time::Duration;
+use reqwest::Response;
+use serde::de::DeserializeOwned;
+use std::sync::Arc;
+
+/// Called before each retry with the upcoming attempt number, the attempt
+/// limit and the error that caused the retry.
+pub type RetryHook = Arc<dyn Fn(u32, u32, &ApiError) + Send + Sync>;
 
 #[derive(Clone)]
 pub struct ApiClient {
     client: reqwest::Client,
     base_url: String,
+    retry: RetryPolicy,
+    on_retry: Option<RetryHook>,
 }
 
 impl ApiClient {
-    pub fn new(base_url: String, token: String) -> Result<Self, ApiError> {
+    pub fn new(
+        base_url: String,
+        token: String,
+        settings: ClientSettings,
+    ) -> Result<Self, ApiError> {
         let mut headers = HeaderMap::new();
         headers.insert(
             "secretkey",
@@ -20,66 +36,146 @@ impl ApiClient {
 
         let client = reqwest::Client::builder()
             .default_headers(headers)
-            .timeout(Duration::from_secs(30))
+            .connect_timeout(settings.connect_timeout)
+            .timeout(settings.timeout)
             .build()?;
 
-        Ok(Self { client, base_url })
+        Ok(Self {
+            client,
+            base_url,
+            retry: settings.retry,
+            on_retry: None,
+        })
     }
 
-    pub async fn list_chores(&self) -> Result<Vec<Chore>, ApiError> {
-        let url = format!("{}/eapi/v1/chore", self.base_url);
-        let response = self.client.get(&url).send().await?;
+    /// Registers a callback that is told about each retry, e.g. to show
+    /// progress while the server is flaky.
+    pub fn on_retry(mut self, hook: impl Fn(u32, u32, &ApiError) + Send + Sync + 'static) -> Self {
+        self.on_retry = Some(Arc::new(hook));
+        self
+    }
+
+    /// GETs `url` and returns the body, retrying connection failures, 5xx
+    /// and 429 responses according to the retry policy. Only used for reads,
+    /// so repeating a request can never apply a change twice.
+    async fn get_text(&self, url: &str) -> Result<String, ApiError> {
+        let mut attempt = 1;
+        loop {
+            let (error, retry_after) = match self.client.get(url).send().await {
+                Ok(response) if response.status().is_success() => {
+                    return Ok(response.text().await?);
+                }
+                Ok(response) => {
+                    let retryable = retry::is_retryable_status(response.status());
+                    let retry_after = retry::retry_after(&response);
+                    let error = error_for(response).await;
+                    if !retryable {
+                        return Err(error);
+                    }
+                    (error, retry_after)
+                }
+                Err(e) if e.is_connect() || e.is_timeout() => (e.into(), None),
+                Err(e) => return Err(e.into()),
+            };
 
-        if !response.status().is_success() {
-            let status = response.status().as_u16();
-            let error_text = response.text().await.unwrap_or_default();
-            return Err(ApiError::ServerError(status, error_text));
+            if attempt >= self.retry.max_attempts {
+                return Err(error);
+            }
+            let Some(delay) = self.retry.delay(attempt, retry_after) else {
+                return Err(error);
+            };
+            attempt += 1;
+            if let Some(hook) = &self.on_retry {
+                hook(attempt, self.retry.max_attempts, &error);
+            }
+            tokio::time::sleep(delay).await;
         }
+    }
+
+    pub async fn list_chores(&self) -> Result<Vec<Chore>, ApiError> {
+        let url = format!("{}/eapi/v1/chore", self.base_url);
+        let text = self.get_text(&url).await?;
 
         // Try to parse as wrapped response first ({"res": [...]})
-        let text = response.text().await?;
         if let Ok(wrapped) = serde_json::from_str::<ChoreListResponse>(&text) {
             return Ok(wrapped.res);
         }
 
         // Fall back to direct array parsing
-        let chores: Vec<Chore> = serde_json::from_str(&text)?;
-        Ok(chores)
+        decode(&text)
     }
 
-    pub async fn create_chore(&self, name: String, due_date: String) -> Result<Chore, ApiError> {
-        let url = format!("{}/eapi/v1/chore", self.base_url);
-        let request = CreateChoreRequest {
-            name,
-            due_date: if due_date.is_empty() {
-                None
-            } else {
-                Some(due_date)
-            },
-        };
-
-        let response = self.client.post(&url).json(&request).send().await?;
-
-        if !response.status().is_success() {
-            let status = response.status().as_u16();
-            let error_text = response.text().await.unwrap_or_default();
-            return Err(ApiError::ServerError(status, error_text));
+    pub async fn chore_history(&self, id: i64) -> Result<Vec<ChoreHistory>, ApiError> {
+        let url = format!("{}/eapi/v1/chore/{}/history", self.base_url, id);
+        let text = self.get_text(&url).await?;
+
+        // Same envelope handling as list_chores
+        if let Ok(wrapped) = serde_json::from_str::<ChoreHistoryResponse>(&text) {
+            return Ok(wrapped.res);
         }
 
-        let chore: Chore = response.json().await?;
-        Ok(chore)
+        decode(&text)
+    }
+
+    pub async fn create_chore(&self, request: CreateChoreRequest) -> Result<Chore, ApiError> {
+        let url = format!("{}/eapi/v1/chore", self.base_url);
+        let response = check(self.client.post(&url).json(&request).send().await?).await?;
+
+        decode(&response.text().await?)
+    }
+
+    pub async fn update_chore(
+        &self,
+        id: i64,
+        request: UpdateChoreRequest,
+    ) -> Result<Chore, ApiError> {
+        let url = format!("{}/eapi/v1/chore/{}", self.base_url, id);
+        let response = check(self.client.put(&url).json(&request).send().await?).await?;
+
+        decode(&response.text().await?)
     }
 
     pub async fn complete_chore(&self, id: i64) -> Result<(), ApiError> {
         let url = format!("{}/eapi/v1/chore/{}/complete", self.base_url, id);
-        let response = self.client.post(&url).send().await?;
+        check(self.client.post(&url).send().await?).await?;
 
-        if !response.status().is_success() {
-            let status = response.status().as_u16();
-            let error_text = response.text().await.unwrap_or_default();
-            return Err(ApiError::ServerError(status, error_text));
-        }
+        Ok(())
+    }
+
+    pub async fn delete_chore(&self, id: i64) -> Result<(), ApiError> {
+        let url = format!("{}/eapi/v1/chore/{}", self.base_url, id);
+        check(self.client.delete(&url).send().await?).await?;
 
         Ok(())
     }
+
+    /// Archives (deactivates) a chore, or restores it when `active` is true.
+    pub async fn set_chore_active(&self, id: i64, active: bool) -> Result<(), ApiError> {
+        let action = if active { "unarchive" } else { "archive" };
+        let url = format!("{}/eapi/v1/chore/{}/{}", self.base_url, id, action);
+        check(self.client.put(&url).send().await?).await?;
+
+        Ok(())
+    }
+}
+
+/// Passes successful responses through and turns the rest into the matching
+/// `ApiError`.
+async fn check(response: Response) -> Result<Response, ApiError> {
+    if response.status().is_success() {
+        Ok(response)
+    } else {
+        Err(error_for(response).await)
+    }
+}
+
+async fn error_for(response: Response) -> ApiError {
+    let status = response.status().as_u16();
+    let retry_after = retry::retry_after(&response);
+    let body = response.text().await.unwrap_or_default();
+    ApiError::from_status(status, &body, retry_after)
+}
+
+fn decode<T: DeserializeOwned>(text: &str) -> Result<T, ApiError> {
+    serde_json::from_str(text).map_err(|e| ApiError::decode(e, text))
 }
@@ -1,31 +1,167 @@
+use serde_json::Value;
 use std::fmt;
+use std::time::Duration;
+
+/// Longest slice of an unparseable response body kept for error messages
+const EXCERPT_LEN: usize = 200;
 
 #[derive(Debug)]
 pub enum ApiError {
     Network(reqwest::Error),
-    Serialization(serde_json::Error),
+    /// The server did not answer within the configured timeout
+    Timeout(reqwest::Error),
+    /// 401: the token is missing, wrong or expired
+    Unauthorized(String),
+    /// 403: the token is valid but may not touch this resource
+    Forbidden(String),
+    /// 404
+    NotFound(String),
+    /// 409: someone else changed the resource first
+    Conflict(String),
+    /// 400/422: the server rejected the request body
+    Validation {
+        message: String,
+        fields: Vec<FieldError>,
+    },
+    /// 429
+    RateLimited {
+        retry_after: Option<Duration>,
+    },
+    /// Any other unsuccessful status
     ServerError(u16, String),
+    /// The response was not the JSON we expected
+    Decode {
+        error: serde_json::Error,
+        excerpt: String,
+    },
     InvalidConfig(String),
 }
 
+/// A single rejected field from a validation error.
+#[derive(Debug, Clone)]
+pub struct FieldError {
+    pub field: String,
+    pub message: String,
+}
+
+impl ApiError {
+    /// Builds the error for an unsuccessful response, pulling the message
+    /// out of Donetick's JSON error body when there is one.
+    pub fn from_status(status: u16, body: &str, retry_after: Option<Duration>) -> Self {
+        let (message, fields) = parse_error_body(body);
+        match status {
+            401 => ApiError::Unauthorized(message),
+            403 => ApiError::Forbidden(message),
+            404 => ApiError::NotFound(message),
+            409 => ApiError::Conflict(message),
+            400 | 422 => ApiError::Validation { message, fields },
+            429 => ApiError::RateLimited { retry_after },
+            _ => ApiError::ServerError(status, message),
+        }
+    }
+
+    /// A JSON decoding failure, keeping the start of the offending body.
+    pub fn decode(error: serde_json::Error, body: &str) -> Self {
+        ApiError::Decode {
+            error,
+            excerpt: excerpt(body),
+        }
+    }
+
+    /// True when the server could not be reached at all, as opposed to it
+    /// answering with an error.
+    pub fn is_offline(&self) -> bool {
+        match self {
+            ApiError::Network(e) => e.is_connect(),
+            ApiError::Timeout(_) => true,
+            _ => false,
+        }
+    }
+
+    /// What the user can do about the error, shown under the message.
+    pub fn hint(&self) -> Option<&'static str> {
+        match self {
+            ApiError::Network(_) => {
+                Some("Check that the server is running and that the configured URL is correct.")
+            }
+            ApiError::Timeout(_) => Some(
+                "The server took too long to answer. Check your connection, or raise `timeout` in the config file.",
+            ),
+            ApiError::Unauthorized(_) => Some(
+                "Token expired or invalid – regenerate it in Settings → Access Token and update your config.",
+            ),
+            ApiError::Forbidden(_) => {
+                Some("Your account is not allowed to do this, e.g. the chore belongs to another circle.")
+            }
+            ApiError::NotFound(_) => {
+                Some("It may have been deleted by someone else – refresh the list.")
+            }
+            ApiError::Conflict(_) => {
+                Some("Someone else changed this chore – refresh and try again.")
+            }
+            ApiError::Validation { .. } => Some("Fix the fields above and submit again."),
+            ApiError::RateLimited { .. } => Some("Too many requests – wait a moment and try again."),
+            ApiError::ServerError(status, _) if *status >= 500 => {
+                Some("The server ran into a problem. Try again later or check its logs.")
+            }
+            ApiError::ServerError(..) => None,
+            ApiError::Decode { .. } => {
+                Some("Unexpected response – is the URL pointing at a Donetick server?")
+            }
+            ApiError::InvalidConfig(_) => Some("Check your .env or config file."),
+        }
+    }
+}
+
 impl fmt::Display for ApiError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             ApiError::Network(e) => write!(f, "Network error: {}", e),
-            ApiError::Serialization(e) => write!(f, "Serialization error: {}", e),
+            ApiError::Timeout(_) => write!(f, "The request timed out"),
+            ApiError::Unauthorized(msg) => with_detail(f, "Unauthorized", msg),
+            ApiError::Forbidden(msg) => with_detail(f, "Forbidden", msg),
+            ApiError::NotFound(msg) => with_detail(f, "Not found", msg),
+            ApiError::Conflict(msg) => with_detail(f, "Conflict", msg),
+            ApiError::Validation { message, fields } => {
+                with_detail(f, "Invalid request", message)?;
+                for field in fields {
+                    write!(f, "\n  {}: {}", field.field, field.message)?;
+                }
+                Ok(())
+            }
+            ApiError::RateLimited { retry_after } => match retry_after {
+                Some(wait) => write!(f, "Rate limited, retry in {}s", wait.as_secs().max(1)),
+                None => write!(f, "Rate limited"),
+            },
             ApiError::ServerError(code, msg) => {
                 write!(f, "Server error ({}): {}", code, msg)
             }
+            ApiError::Decode { error, excerpt } if excerpt.is_empty() => {
+                write!(f, "Could not read the server's response: {}", error)
+            }
+            ApiError::Decode { error, excerpt } => write!(
+                f,
+                "Could not read the server's response: {}\nResponse began: {}",
+                error, excerpt
+            ),
             ApiError::InvalidConfig(msg) => write!(f, "Invalid configuration: {}", msg),
         }
     }
 }
 
+fn with_detail(f: &mut fmt::Formatter<'_>, summary: &str, detail: &str) -> fmt::Result {
+    if detail.is_empty() {
+        write!(f, "{}", summary)
+    } else {
+        write!(f, "{}: {}", summary, detail)
+    }
+}
+
 impl std::error::Error for ApiError {
     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
         match self {
-            ApiError::Network(e) => Some(e),
-            ApiError::Serialization(e) => Some(e),
+            ApiError::Network(e) | ApiError::Timeout(e) => Some(e),
+            ApiError::Decode { error, .. } => Some(error),
             _ => None,
         }
     }
@@ -33,12 +169,67 @@ impl std::error::Error for ApiError {
 
 impl From<reqwest::Error> for ApiError {
     fn from(err: reqwest::Error) -> Self {
-        ApiError::Network(err)
+        if err.is_timeout() {
+            ApiError::Timeout(err)
+        } else {
+            ApiError::Network(err)
+        }
+    }
+}
+
+/// Donetick answers errors with `{"error": "..."}`; some endpoints use
+/// `message` instead, and validation failures may add an `errors` object
+/// keyed by field. Anything else is shown as (an excerpt of) plain text.
+fn parse_error_body(body: &str) -> (String, Vec<FieldError>) {
+    let Ok(Value::Object(json)) = serde_json::from_str::<Value>(body) else {
+        return (excerpt(body), Vec::new());
+    };
+
+    let message = ["error", "message", "msg"]
+        .iter()
+        .find_map(|key| json.get(*key).and_then(Value::as_str))
+        .unwrap_or_default()
+        .to_string();
+
+    let fields = match json.get("errors") {
+        Some(Value::Object(errors)) => errors
+            .iter()
+            .map(|(field, value)| FieldError {
+                field: field.clone(),
+                message: field_message(value),
+            })
+            .collect(),
+        Some(Value::Array(errors)) => errors
+            .iter()
+            .filter_map(|error| {
+                Some(FieldError {
+                    field: error.get("field")?.as_str()?.to_string(),
+                    message: error.get("message").map(field_message).unwrap_or_default(),
+                })
+            })
+            .collect(),
+        _ => Vec::new(),
+    };
+
+    (message, fields)
+}
+
+fn field_message(value: &Value) -> String {
+    match value {
+        Value::String(message) => message.clone(),
+        Value::Array(messages) => messages
+            .iter()
+            .map(field_message)
+            .collect::<Vec<_>>()
+            .join(", "),
+        other => other.to_string(),
     }
 }
 
-impl From<serde_json::Error> for ApiError {
-    fn from(err: serde_json::Error) -> Self {
-        ApiError::Serialization(err)
+fn excerpt(body: &str) -> String {
+    let body = body.trim();
+    match body.char_indices().nth(EXCERPT_LEN) {
+        Some((end, _)) => format!("{}…", &body[..end]),
+        None => body.to_string(),
     }
 }
@@ -1,6 +1,10 @@
+pub mod chore_api;
 pub mod client;
 pub mod error;
+pub mod retry;
 pub mod types;
 
+pub use chore_api::ChoreApi;
 pub use client::ApiClient;
-pub use types::{Chore, ChoreListResponse};
+pub use retry::ClientSettings;
+pub use types::{Chore, ChoreHistory, CreateChoreRequest, UpdateChoreRequest};
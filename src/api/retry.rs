@@ -0,0 +1,92 @@
+use chrono::{DateTime, Utc};
+use reqwest::header::RETRY_AFTER;
+use reqwest::{Response, StatusCode};
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::time::Duration;
+
+/// Timeouts and retry behaviour for an `ApiClient`.
+#[derive(Debug, Clone)]
+pub struct ClientSettings {
+    /// Time allowed to establish a connection
+    pub connect_timeout: Duration,
+    /// Time allowed for a whole request, including reading the body
+    pub timeout: Duration,
+    pub retry: RetryPolicy,
+}
+
+impl Default for ClientSettings {
+    fn default() -> Self {
+        Self {
+            connect_timeout: Duration::from_secs(10),
+            timeout: Duration::from_secs(30),
+            retry: RetryPolicy::default(),
+        }
+    }
+}
+
+/// Exponential backoff for idempotent requests. Attempt `n` waits roughly
+/// `base_delay * 2^(n-1)`, randomised by up to half so clients that failed
+/// together do not retry together.
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    /// Total attempts including the first; 1 disables retries
+    pub max_attempts: u32,
+    pub base_delay: Duration,
+    /// Upper bound for a single wait. A `Retry-After` longer than this is
+    /// not waited out; the request fails instead.
+    pub max_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 5,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(30),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// How long to wait after failed attempt number `attempt` (1-based), or
+    /// None when the server asked for a longer pause than we are willing to
+    /// wait.
+    pub fn delay(&self, attempt: u32, retry_after: Option<Duration>) -> Option<Duration> {
+        if let Some(wait) = retry_after {
+            return (wait <= self.max_delay).then_some(wait);
+        }
+
+        let exponent = attempt.saturating_sub(1).min(16);
+        let backoff = self
+            .base_delay
+            .saturating_mul(1 << exponent)
+            .min(self.max_delay);
+        let half = backoff / 2;
+        Some(half + half.mul_f64(random_fraction()))
+    }
+}
+
+/// Connection failures, server errors and rate limiting are worth another try.
+pub fn is_retryable_status(status: StatusCode) -> bool {
+    status.is_server_error() || status == StatusCode::TOO_MANY_REQUESTS
+}
+
+/// Reads `Retry-After` as either delay seconds or an HTTP date.
+pub fn retry_after(response: &Response) -> Option<Duration> {
+    let value = response.headers().get(RETRY_AFTER)?.to_str().ok()?.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+    let at = DateTime::parse_from_rfc2822(value)
+        .ok()?
+        .with_timezone(&Utc);
+    Some((at - Utc::now()).to_std().unwrap_or_default())
+}
+
+/// A value in [0, 1). Jitter does not need a real RNG; the randomly keyed
+/// std hasher is plenty.
+fn random_fraction() -> f64 {
+    let bits = RandomState::new().build_hasher().finish();
+    (bits >> 11) as f64 / (1u64 << 53) as f64
+}
@@ -1,4 +1,5 @@
-use serde::{Deserialize, Serialize};
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
+use serde::{Deserialize, Deserializer, Serialize};
 use serde_json::Value;
 
 // Response wrapper for list endpoint
@@ -7,7 +8,7 @@ pub struct ChoreListResponse {
     pub res: Vec<Chore>,
 }
 
-#[derive(Debug, Clone, Deserialize, Serialize)]
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Chore {
     #[serde(default)]
@@ -18,8 +19,8 @@ pub struct Chore {
     pub frequency_type: Option<String>,
     #[serde(default)]
     pub frequency: Option<i32>,
-    #[serde(default)]
-    pub next_due_date: Option<String>,
+    #[serde(default, deserialize_with = "lenient_timestamp")]
+    pub next_due_date: Option<DateTime<Utc>>,
     #[serde(default)]
     pub assigned_to: Option<i64>,
     #[serde(default)]
@@ -31,15 +32,15 @@ pub struct Chore {
     #[serde(default)]
     pub circle_id: Option<i64>,
     #[serde(default)]
-    pub status: Option<i32>,  // Changed to i32 from String
+    pub status: Option<i32>, // Changed to i32 from String
     #[serde(default)]
     pub priority: Option<i32>,
     #[serde(default)]
     pub description: Option<String>,
-    #[serde(default)]
-    pub created_at: Option<String>,
-    #[serde(default)]
-    pub updated_at: Option<String>,
+    #[serde(default, deserialize_with = "lenient_timestamp")]
+    pub created_at: Option<DateTime<Utc>>,
+    #[serde(default, deserialize_with = "lenient_timestamp")]
+    pub updated_at: Option<DateTime<Utc>>,
     // Additional fields from API that we'll ignore
     #[serde(default)]
     pub is_rolling: Option<bool>,
@@ -65,7 +66,51 @@ pub struct Chore {
     pub labels: Option<Value>,
 }
 
-#[derive(Debug, Clone, Deserialize, Serialize)]
+impl Chore {
+    /// Human readable status; archived chores report "archived" regardless of status.
+    pub fn status_name(&self) -> &'static str {
+        match self.status {
+            _ if self.is_active == Some(false) => "archived",
+            Some(1) => "active",
+            Some(2) => "completed",
+            Some(0) | None => "pending",
+            Some(_) => "unknown",
+        }
+    }
+}
+
+/// Parses the timestamp formats Donetick emits: RFC 3339, naive date-times
+/// and bare dates (both taken as UTC).
+pub fn parse_timestamp(value: &str) -> Option<DateTime<Utc>> {
+    if let Ok(at) = DateTime::parse_from_rfc3339(value) {
+        return Some(at.with_timezone(&Utc));
+    }
+    if let Ok(at) = NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S%.f") {
+        return Some(at.and_utc());
+    }
+    let date = NaiveDate::parse_from_str(value, "%Y-%m-%d").ok()?;
+    Some(date.and_hms_opt(0, 0, 0)?.and_utc())
+}
+
+// Unparseable timestamps become None instead of failing the whole response
+fn lenient_timestamp<'de, D>(deserializer: D) -> Result<Option<DateTime<Utc>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let value: Option<String> = Option::deserialize(deserializer)?;
+    Ok(value.as_deref().and_then(parse_timestamp))
+}
+
+// Tells a field set to null apart from a missing one, which `default` makes None
+fn present<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    T::deserialize(deserializer).map(Some)
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 pub struct Assignee {
     #[serde(default)]
     pub id: i64,
@@ -73,10 +118,81 @@ pub struct Assignee {
     pub name: String,
 }
 
-#[derive(Debug, Serialize)]
+// Response wrapper for the history endpoint
+#[derive(Debug, Clone, Deserialize)]
+pub struct ChoreHistoryResponse {
+    pub res: Vec<ChoreHistory>,
+}
+
+// One completion of a chore
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChoreHistory {
+    #[serde(default)]
+    pub id: i64,
+    #[serde(default)]
+    pub chore_id: i64,
+    #[serde(default, alias = "completedAt", deserialize_with = "lenient_timestamp")]
+    pub performed_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub completed_by: Option<i64>,
+    #[serde(default)]
+    pub assigned_to: Option<i64>,
+    #[serde(default)]
+    pub notes: Option<String>,
+    #[serde(default, deserialize_with = "lenient_timestamp")]
+    pub due_date: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(rename_all = "PascalCase")]
 pub struct CreateChoreRequest {
     pub name: String,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub due_date: Option<String>,
+    pub due_date: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub priority: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub labels: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub frequency_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub frequency: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub frequency_metadata: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_rolling: Option<bool>,
+}
+
+// Partial update: fields left as None are not sent and stay unchanged on the server
+#[derive(Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct UpdateChoreRequest {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    // Some(None) is sent as null, which removes the due date
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "present"
+    )]
+    pub due_date: Option<Option<DateTime<Utc>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub priority: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub labels: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub frequency_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub frequency: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub frequency_metadata: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_rolling: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub assigned_to: Option<i64>,
 }
@@ -1,25 +1,209 @@
-use crate::api::Chore;
+use crate::api::error::ApiError;
+use crate::api::{Chore, ChoreHistory, CreateChoreRequest, UpdateChoreRequest};
+use crate::bulk::{BulkAction, BulkResult};
+use crate::dates;
+use crate::diff::{self, Change};
+use crate::filter::Filter;
+use crate::keymap::{Action, KeyPress, Keymap};
+use crate::offline::{self, Operation, Outbox};
+use crate::recurrence::Recurrence;
+use crate::search;
+use crate::sort::SortOrder;
+use crate::state::SavedState;
+use crate::theme::Theme;
+use crate::undo::{Revert, Undo, UndoStack};
+use chrono::{DateTime, Local, Utc};
+use ratatui::layout::Rect;
+use std::collections::{HashMap, HashSet};
+use std::time::{Duration, Instant};
+
+/// How long rows stay highlighted after a reload changed them
+const HIGHLIGHT: Duration = Duration::from_secs(5);
 
 pub struct App {
     pub running: bool,
     pub view: View,
     pub tasks: Vec<Chore>,
+    /// Indices into `tasks` of the rows currently shown, in display order
+    pub visible: Vec<usize>,
+    /// Position of the selection within `visible`
     pub selected_task: usize,
+    /// First visible row of the task table, kept between frames so the
+    /// view only scrolls when the selection leaves it
+    pub scroll_offset: usize,
+    /// Task rows that fit on screen, as of the last draw
+    pub page_size: usize,
+    /// Where the clickable parts of the screen were, as of the last draw
+    pub regions: Regions,
+    pub search: SearchState,
+    pub filter: FilterState,
+    pub sort: SortOrder,
+    /// Name or id that `assignee:me` filters resolve to
+    pub current_user: Option<String>,
     pub form_state: FormState,
     pub loading: bool,
     pub error_message: Option<String>,
+    /// Suggested fix shown under the error message
+    pub error_hint: Option<String>,
+    /// Transient message shown under the task list
+    pub status: Option<String>,
+    /// Progress of a request that is being retried, e.g. "Retrying (2/5)…"
+    pub retrying: Option<String>,
+    /// Set while the server is unreachable and cached tasks are shown
+    pub offline: bool,
+    pub outbox: Outbox,
+    pub syncing: bool,
+    pub retry_pending: bool,
+    pub confirmation: Option<Confirmation>,
+    pub show_details: bool,
+    pub history: HistoryState,
+    /// Config file profile the client is connected to, if any
+    pub profile: Option<String>,
+    /// Bumped on every profile switch, so results of work started for an
+    /// earlier profile can be told apart and dropped
+    pub generation: u64,
+    pub profiles: ProfileState,
+    /// Ids of the chores marked for a bulk action
+    pub marked: HashSet<i64>,
+    /// Row where `V` started a range selection, while one is open
+    pub visual_anchor: Option<usize>,
+    pub bulk_prompt: BulkPrompt,
+    /// Set while a bulk action is running
+    pub bulk_progress: Option<BulkProgress>,
+    pub bulk_summary: BulkSummary,
+    /// Changes `u` can revert, most recent last
+    pub undo: UndoStack,
+    /// How often the list reloads on its own; None turns it off
+    pub refresh_interval: Option<Duration>,
+    /// When the list last arrived from the server
+    pub refreshed_at: Option<Instant>,
+    pub highlight: Highlight,
+    pub keymap: Keymap,
+    pub theme: Theme,
+    /// Keys typed so far of a multi-key binding such as `gg`
+    pub pending_keys: Vec<KeyPress>,
+    /// First line shown on the help screen
+    pub help_scroll: u16,
+}
+
+/// Screen areas the mouse can act on. Each draw starts from empty, so
+/// anything not on screen cannot be clicked.
+#[derive(Debug, Default)]
+pub struct Regions {
+    /// The task table's rows, below its header
+    pub task_rows: Rect,
+    pub form_fields: Vec<(FormField, Rect)>,
+    /// Footer hints and the action each one runs
+    pub hints: Vec<(Rect, Action)>,
+}
+
+/// Rows that changed in the last background refresh, shown in colour until
+/// `until`.
+#[derive(Debug, Default)]
+pub struct Highlight {
+    pub changes: HashMap<i64, Change>,
+    /// Chores the reload no longer had, shown struck through below the list
+    pub removed: Vec<Chore>,
+    pub until: Option<Instant>,
 }
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum View {
     TaskList,
     AddTask,
+    EditTask,
     ErrorDialog,
+    Confirm,
+    History,
+    Profiles,
+    /// Asks for the new labels, assignee or due date of the marked chores
+    BulkEdit,
+    /// Per-chore results of the last bulk action
+    BulkSummary,
+    /// Every key binding, generated from the keymap
+    Help,
+}
+
+/// Incremental search over the task list, entered with `/`.
+pub struct SearchState {
+    pub query: String,
+    pub editing: bool,
+}
+
+/// Structured filter typed into the filter bar, entered with `f`.
+pub struct FilterState {
+    pub input: String,
+    pub editing: bool,
+    /// Last successfully parsed filter; stays applied while the input has errors
+    pub active: Filter,
+    pub error: Option<String>,
+}
+
+/// Completion history of a single chore, shown by `View::History`.
+pub struct HistoryState {
+    pub chore_id: i64,
+    pub chore_name: String,
+    pub entries: Vec<ChoreHistory>,
+    pub selected: usize,
+    pub loading: bool,
+}
+
+/// Profile picker shown by `View::Profiles`.
+pub struct ProfileState {
+    pub names: Vec<String>,
+    pub selected: usize,
+}
+
+/// Which field `View::BulkEdit` sets on the marked chores.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BulkField {
+    Labels,
+    Assignee,
+    DueDate,
+}
+
+pub struct BulkPrompt {
+    pub field: BulkField,
+    pub input: String,
+    pub error: Option<String>,
+}
+
+pub struct BulkProgress {
+    pub verb: &'static str,
+    pub done: usize,
+    pub total: usize,
+}
+
+pub struct BulkSummary {
+    /// Past tense of the action, e.g. "Completed"
+    pub past: &'static str,
+    pub results: Vec<BulkResult>,
+}
+
+/// A pending yes/no prompt shown by `View::Confirm`.
+#[derive(Debug, Clone)]
+pub struct Confirmation {
+    pub message: String,
+    pub action: ConfirmAction,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConfirmAction {
+    DeleteTask(i64),
+    SetTaskActive(i64, bool),
+    /// Delete every marked chore
+    BulkDelete,
 }
 
 pub struct FormState {
+    pub editing_id: Option<i64>,
     pub name: String,
     pub due_date: String,
+    pub description: String,
+    pub priority: String,
+    pub labels: String,
+    pub recurrence: String,
+    pub rolling: bool,
     pub cursor_position: usize,
     pub active_field: FormField,
 }
@@ -28,6 +212,41 @@ pub struct FormState {
 pub enum FormField {
     Name,
     DueDate,
+    Description,
+    Priority,
+    Labels,
+    Recurrence,
+    Rolling,
+}
+
+impl FormField {
+    pub const ALL: [FormField; 7] = [
+        FormField::Name,
+        FormField::DueDate,
+        FormField::Description,
+        FormField::Priority,
+        FormField::Labels,
+        FormField::Recurrence,
+        FormField::Rolling,
+    ];
+
+    fn index(&self) -> usize {
+        Self::ALL.iter().position(|f| f == self).unwrap_or(0)
+    }
+
+    pub fn next(&self) -> FormField {
+        Self::ALL[(self.index() + 1) % Self::ALL.len()].clone()
+    }
+
+    pub fn previous(&self) -> FormField {
+        Self::ALL[(self.index() + Self::ALL.len() - 1) % Self::ALL.len()].clone()
+    }
+}
+
+impl Default for App {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl App {
@@ -36,31 +255,592 @@ impl App {
             running: true,
             view: View::TaskList,
             tasks: Vec::new(),
+            visible: Vec::new(),
             selected_task: 0,
+            scroll_offset: 0,
+            page_size: 10,
+            regions: Regions::default(),
+            search: SearchState {
+                query: String::new(),
+                editing: false,
+            },
+            filter: FilterState {
+                input: String::new(),
+                editing: false,
+                active: Filter::default(),
+                error: None,
+            },
+            sort: SortOrder::default(),
+            current_user: None,
             form_state: FormState::new(),
             loading: true,
             error_message: None,
+            error_hint: None,
+            status: None,
+            retrying: None,
+            offline: false,
+            outbox: Outbox::default(),
+            syncing: false,
+            retry_pending: false,
+            confirmation: None,
+            show_details: false,
+            history: HistoryState::new(),
+            profile: None,
+            generation: 0,
+            profiles: ProfileState {
+                names: Vec::new(),
+                selected: 0,
+            },
+            marked: HashSet::new(),
+            visual_anchor: None,
+            bulk_prompt: BulkPrompt {
+                field: BulkField::Labels,
+                input: String::new(),
+                error: None,
+            },
+            bulk_progress: None,
+            bulk_summary: BulkSummary {
+                past: "",
+                results: Vec::new(),
+            },
+            undo: UndoStack::default(),
+            refresh_interval: None,
+            refreshed_at: None,
+            highlight: Highlight::default(),
+            keymap: Keymap::default(),
+            theme: Theme::default(),
+            pending_keys: Vec::new(),
+            help_scroll: 0,
+        }
+    }
+
+    /// Points the app at a profile's cached tasks and outbox. Used on startup
+    /// and whenever the profile switcher picks a different server.
+    pub fn open_profile(&mut self, profile: Option<String>, user: Option<String>) {
+        self.profile = profile;
+        self.generation += 1;
+        self.current_user = user;
+        self.outbox = Outbox::load(self.profile.as_deref());
+        self.offline = false;
+        self.syncing = false;
+        self.loading = true;
+        self.tasks.clear();
+        self.marked.clear();
+        self.visual_anchor = None;
+        // A bulk action on the previous server never reports back here
+        self.bulk_progress = None;
+        // Undo entries name chores on the previous server
+        self.undo.clear();
+        // `assignee:me` may now mean someone else
+        self.apply_filter();
+        // Show the last known list straight away; the network refresh replaces it
+        if let Some(cached) = offline::load_cached_chores(self.profile.as_deref()) {
+            self.set_tasks(cached);
+        } else {
+            self.refresh_visible(None);
         }
     }
 
+    pub fn show_profiles(&mut self, names: Vec<String>) {
+        self.profiles.selected = self
+            .profile
+            .as_ref()
+            .and_then(|current| names.iter().position(|name| name == current))
+            .unwrap_or(0);
+        self.profiles.names = names;
+        self.view = View::Profiles;
+    }
+
+    pub fn selected_profile(&self) -> Option<&str> {
+        self.profiles
+            .names
+            .get(self.profiles.selected)
+            .map(String::as_str)
+    }
+
+    pub fn close_profiles(&mut self) {
+        self.view = View::TaskList;
+    }
+
     pub fn next_task(&mut self) {
-        if !self.tasks.is_empty() {
-            self.selected_task = (self.selected_task + 1) % self.tasks.len();
+        if !self.visible.is_empty() {
+            self.selected_task = (self.selected_task + 1) % self.visible.len();
         }
     }
 
     pub fn previous_task(&mut self) {
-        if !self.tasks.is_empty() {
+        if !self.visible.is_empty() {
             if self.selected_task == 0 {
-                self.selected_task = self.tasks.len() - 1;
+                self.selected_task = self.visible.len() - 1;
             } else {
                 self.selected_task -= 1;
             }
         }
     }
 
+    /// Moves the selection down a screenful, stopping at the last task.
+    pub fn page_down(&mut self) {
+        if !self.visible.is_empty() {
+            self.selected_task =
+                (self.selected_task + self.page_size.max(1)).min(self.visible.len() - 1);
+        }
+    }
+
+    /// Moves the selection up a screenful, stopping at the first task.
+    pub fn page_up(&mut self) {
+        self.selected_task = self.selected_task.saturating_sub(self.page_size.max(1));
+    }
+
+    /// Selects the task at `row` of the visible list, if there is one.
+    pub fn select_row(&mut self, row: usize) {
+        if row < self.visible.len() {
+            self.selected_task = row;
+        }
+    }
+
+    /// Scrolls the list by `rows` without moving the selection, unless it
+    /// would leave the screen, in which case it is dragged along.
+    pub fn scroll_by(&mut self, rows: isize) {
+        let page = self.page_size.max(1);
+        let last_offset = self.visible.len().saturating_sub(page);
+        self.scroll_offset = self
+            .scroll_offset
+            .saturating_add_signed(rows)
+            .min(last_offset);
+        self.selected_task = self
+            .selected_task
+            .clamp(self.scroll_offset, self.scroll_offset + page - 1)
+            .min(self.visible.len().saturating_sub(1));
+    }
+
+    pub fn first_task(&mut self) {
+        self.selected_task = 0;
+    }
+
+    pub fn last_task(&mut self) {
+        self.selected_task = self.visible.len().saturating_sub(1);
+    }
+
+    /// Marks or unmarks the selected chore and moves to the next row.
+    pub fn toggle_mark(&mut self) {
+        let Some(id) = self.select_task().map(|t| t.id) else {
+            return;
+        };
+        if !self.marked.remove(&id) {
+            self.marked.insert(id);
+        }
+        if self.selected_task + 1 < self.visible.len() {
+            self.selected_task += 1;
+        }
+    }
+
+    /// Starts a range selection at the selected row, or marks the rows
+    /// between its start and the selection when one is open.
+    pub fn toggle_visual(&mut self) {
+        match self.visual_anchor.take() {
+            Some(anchor) => {
+                let ids: Vec<i64> = self.visual_range(anchor).map(|t| t.id).collect();
+                self.marked.extend(ids);
+            }
+            None if !self.visible.is_empty() => self.visual_anchor = Some(self.selected_task),
+            None => {}
+        }
+    }
+
+    fn visual_range(&self, anchor: usize) -> impl Iterator<Item = &Chore> {
+        let (start, end) = if anchor <= self.selected_task {
+            (anchor, self.selected_task)
+        } else {
+            (self.selected_task, anchor)
+        };
+        self.visible_tasks().skip(start).take(end + 1 - start)
+    }
+
+    /// Whether the row at `position` in `visible` is marked or inside the
+    /// open range selection.
+    pub fn is_marked(&self, position: usize) -> bool {
+        let in_range = self.visual_anchor.is_some_and(|anchor| {
+            (anchor.min(self.selected_task)..=anchor.max(self.selected_task)).contains(&position)
+        });
+        in_range
+            || self
+                .visible
+                .get(position)
+                .is_some_and(|&index| self.marked.contains(&self.tasks[index].id))
+    }
+
+    pub fn has_marks(&self) -> bool {
+        !self.marked.is_empty() || self.visual_anchor.is_some()
+    }
+
+    /// Drops the open range selection, or every mark when there is none.
+    pub fn clear_marks(&mut self) {
+        if self.visual_anchor.take().is_none() {
+            self.marked.clear();
+        }
+    }
+
+    /// The chores a bulk action applies to: the marked ones (including an
+    /// open range) in display order, or just the selected chore.
+    pub fn bulk_targets(&self) -> Vec<Chore> {
+        if !self.has_marks() {
+            return self.select_task().cloned().into_iter().collect();
+        }
+        let mut targets: Vec<Chore> = self
+            .tasks
+            .iter()
+            .filter(|t| self.marked.contains(&t.id))
+            .cloned()
+            .collect();
+        if let Some(anchor) = self.visual_anchor {
+            targets.extend(self.visual_range(anchor).cloned());
+        }
+        // Keep the order the user sees, without duplicates
+        let order = |id: i64| {
+            self.visible_tasks()
+                .position(|t| t.id == id)
+                .unwrap_or(usize::MAX)
+        };
+        targets.sort_by_key(|t| order(t.id));
+        targets.dedup_by_key(|t| t.id);
+        targets
+    }
+
+    pub fn start_bulk_edit(&mut self, field: BulkField) {
+        if self.bulk_targets().is_empty() {
+            return;
+        }
+        self.bulk_prompt = BulkPrompt {
+            field,
+            input: String::new(),
+            error: None,
+        };
+        self.view = View::BulkEdit;
+    }
+
+    /// Resolves the assignee typed into the bulk prompt: a user id, or the
+    /// name of someone already assigned to a chore.
+    pub fn resolve_user(&self, input: &str) -> Option<i64> {
+        let input = input.trim();
+        if let Ok(id) = input.parse() {
+            return Some(id);
+        }
+        self.tasks
+            .iter()
+            .flat_map(|t| t.assignees.iter().flatten())
+            .find(|a| a.name.eq_ignore_ascii_case(input))
+            .map(|a| a.id)
+    }
+
+    pub fn begin_bulk(&mut self, action: &BulkAction, total: usize) {
+        self.bulk_progress = Some(BulkProgress {
+            verb: action.verb(),
+            done: 0,
+            total,
+        });
+    }
+
+    /// Shows the results of a bulk action. Chores that failed stay marked
+    /// so the action can be retried on just those.
+    pub fn finish_bulk(&mut self, action: &BulkAction, results: Vec<BulkResult>) {
+        self.bulk_progress = None;
+        self.visual_anchor = None;
+        // The list still holds the chores as they were before the action
+        let reverts: Vec<Revert> = results
+            .iter()
+            .filter(|r| r.error.is_none())
+            .filter_map(|r| self.task(r.id).cloned())
+            .map(|chore| match action {
+                BulkAction::Complete => Revert::Complete(chore),
+                BulkAction::Delete => Revert::Delete(chore),
+                _ => Revert::Edit(chore),
+            })
+            .collect();
+        if !reverts.is_empty() {
+            self.push_undo(Undo::bulk(action.past(), reverts));
+        }
+        self.marked = results
+            .iter()
+            .filter(|r| r.error.is_some())
+            .map(|r| r.id)
+            .collect();
+        self.bulk_summary = BulkSummary {
+            past: action.past(),
+            results,
+        };
+        self.view = View::BulkSummary;
+    }
+
+    /// Records a change that `u` can revert and says so in the status line.
+    pub fn push_undo(&mut self, undo: Undo) {
+        self.notify(undo.toast());
+        self.undo.push(undo);
+    }
+
+    pub fn task(&self, id: i64) -> Option<&Chore> {
+        self.tasks.iter().find(|t| t.id == id)
+    }
+
     pub fn select_task(&self) -> Option<&Chore> {
-        self.tasks.get(self.selected_task)
+        self.visible
+            .get(self.selected_task)
+            .and_then(|&index| self.tasks.get(index))
+    }
+
+    pub fn visible_tasks(&self) -> impl Iterator<Item = &Chore> {
+        self.visible
+            .iter()
+            .filter_map(|&index| self.tasks.get(index))
+    }
+
+    /// Replaces the list, keeping the selected chore selected. Any highlight
+    /// from an earlier refresh goes, since it no longer describes this list.
+    pub fn set_tasks(&mut self, tasks: Vec<Chore>) {
+        self.highlight = Highlight::default();
+        self.replace_tasks(tasks);
+    }
+
+    /// Like `set_tasks`, but also highlights what changed since the previous
+    /// list. Meant for reloads nobody here asked for, where the changes were
+    /// made elsewhere.
+    pub fn refresh_tasks(&mut self, tasks: Vec<Chore>) {
+        if !self.tasks.is_empty() {
+            self.highlight_changes(&tasks, Instant::now());
+        }
+        self.replace_tasks(tasks);
+    }
+
+    fn replace_tasks(&mut self, tasks: Vec<Chore>) {
+        let keep = self.select_task().map(|t| t.id);
+        self.tasks = tasks;
+        self.tasks.extend(self.outbox.placeholders());
+        self.loading = false;
+        let tasks = &self.tasks;
+        self.marked.retain(|id| tasks.iter().any(|t| t.id == *id));
+        self.refresh_visible(keep);
+    }
+
+    fn highlight_changes(&mut self, tasks: &[Chore], now: Instant) {
+        let changes = diff::diff(&self.tasks, tasks);
+        if changes.is_empty() {
+            return;
+        }
+        self.highlight.removed = self
+            .tasks
+            .iter()
+            .filter(|t| changes.contains(&(t.id, Change::Removed)))
+            .cloned()
+            .collect();
+        if self.status.is_none() {
+            if let Some(summary) = diff::summary(&changes) {
+                self.notify(format!("Updated from server: {}", summary));
+            }
+        }
+        self.highlight.changes = changes.into_iter().collect();
+        self.highlight.until = Some(now + HIGHLIGHT);
+    }
+
+    /// Drops the change highlight once it has been shown long enough.
+    pub fn expire_highlight(&mut self, now: Instant) {
+        if self.highlight.until.is_some_and(|until| now >= until) {
+            self.highlight = Highlight::default();
+        }
+    }
+
+    pub fn change(&self, id: i64) -> Option<Change> {
+        self.highlight.changes.get(&id).copied()
+    }
+
+    /// Whether the periodic refresh should reload the list now. It waits
+    /// while anything else is loading or a range selection is open, since
+    /// a reload would reset the range.
+    pub fn refresh_due(&self, now: Instant) -> bool {
+        let Some(interval) = self.refresh_interval else {
+            return false;
+        };
+        let idle = !self.loading
+            && !self.offline
+            && !self.syncing
+            && self.bulk_progress.is_none()
+            && self.visual_anchor.is_none();
+        match self.refreshed_at {
+            Some(at) => idle && now.duration_since(at) >= interval,
+            None => idle,
+        }
+    }
+
+    /// Queues an operation made while offline and shows it in the list.
+    pub fn queue_offline(&mut self, operation: Operation) {
+        let description = operation.describe();
+        self.outbox.push(operation);
+        self.offline = true;
+        self.loading = false;
+        self.view = View::TaskList;
+        match self.outbox.save() {
+            Ok(()) => self.notify(format!("Offline: queued {}", description)),
+            Err(e) => self.notify(format!(
+                "Offline: queued {} but could not save it: {}",
+                description, e
+            )),
+        }
+
+        // Rebuild the placeholder rows so a queued create shows up immediately
+        let keep = self.select_task().map(|t| t.id);
+        self.tasks.retain(|t| t.id >= 0);
+        self.tasks.extend(self.outbox.placeholders());
+        self.refresh_visible(keep);
+    }
+
+    pub fn notify(&mut self, message: String) {
+        self.status = Some(message);
+    }
+
+    /// Recomputes the visible rows, keeping the chore with id `keep` selected
+    /// when it is still shown and clamping the selection otherwise.
+    fn refresh_visible(&mut self, keep: Option<i64>) {
+        // Row positions are about to change under the range
+        self.visual_anchor = None;
+        let query = &self.search.query;
+        let filter = &self.filter.active;
+        let now = Local::now();
+        let mut scored: Vec<(usize, i64)> = self
+            .tasks
+            .iter()
+            .enumerate()
+            .filter(|(_, task)| filter.matches(task, now))
+            .filter_map(|(index, task)| Some((index, search::score_chore(query, task)?)))
+            .collect();
+
+        // Best matches first while searching; every score is 0 otherwise
+        let tasks = &self.tasks;
+        let sort = self.sort;
+        scored.sort_by(|&(a, a_score), &(b, b_score)| {
+            b_score
+                .cmp(&a_score)
+                .then_with(|| sort.compare(&tasks[a], &tasks[b]))
+        });
+        self.visible = scored.into_iter().map(|(index, _)| index).collect();
+
+        let kept = keep.and_then(|id| {
+            self.visible
+                .iter()
+                .position(|&index| self.tasks[index].id == id)
+        });
+        self.selected_task = match kept {
+            Some(position) => position,
+            None if self.selected_task >= self.visible.len() => 0,
+            None => self.selected_task,
+        };
+    }
+
+    pub fn start_filter(&mut self) {
+        self.filter.editing = true;
+    }
+
+    pub fn finish_filter(&mut self) {
+        self.filter.editing = false;
+    }
+
+    pub fn push_filter_char(&mut self, c: char) {
+        self.filter.input.push(c);
+        self.apply_filter();
+    }
+
+    pub fn pop_filter_char(&mut self) {
+        self.filter.input.pop();
+        self.apply_filter();
+    }
+
+    pub fn clear_filter(&mut self) {
+        self.filter.input.clear();
+        self.filter.editing = false;
+        self.apply_filter();
+    }
+
+    fn apply_filter(&mut self) {
+        match Filter::parse(&self.filter.input, self.current_user.as_deref()) {
+            Ok(filter) => {
+                let keep = self.select_task().map(|t| t.id);
+                self.filter.active = filter;
+                self.filter.error = None;
+                self.refresh_visible(keep);
+            }
+            Err(message) => self.filter.error = Some(message),
+        }
+    }
+
+    pub fn cycle_sort_key(&mut self) {
+        let keep = self.select_task().map(|t| t.id);
+        self.sort.key = self.sort.key.next();
+        self.refresh_visible(keep);
+    }
+
+    pub fn toggle_sort_direction(&mut self) {
+        let keep = self.select_task().map(|t| t.id);
+        self.sort.descending = !self.sort.descending;
+        self.refresh_visible(keep);
+    }
+
+    pub fn restore_state(&mut self, state: SavedState) {
+        self.sort = state.sort;
+    }
+
+    /// Snapshot of the preferences that are persisted between runs.
+    pub fn saved_state(&self) -> SavedState {
+        SavedState { sort: self.sort }
+    }
+
+    pub fn start_search(&mut self) {
+        self.search.editing = true;
+    }
+
+    pub fn finish_search(&mut self) {
+        self.search.editing = false;
+    }
+
+    pub fn push_search_char(&mut self, c: char) {
+        let keep = self.select_task().map(|t| t.id);
+        self.search.query.push(c);
+        self.refresh_visible(keep);
+    }
+
+    pub fn pop_search_char(&mut self) {
+        let keep = self.select_task().map(|t| t.id);
+        self.search.query.pop();
+        self.refresh_visible(keep);
+    }
+
+    pub fn clear_search(&mut self) {
+        let keep = self.select_task().map(|t| t.id);
+        self.search.query.clear();
+        self.search.editing = false;
+        self.refresh_visible(keep);
+    }
+
+    pub fn toggle_details(&mut self) {
+        self.show_details = !self.show_details;
+    }
+
+    pub fn show_history(&mut self) -> Option<i64> {
+        let task = self.select_task()?;
+        self.history = HistoryState {
+            chore_id: task.id,
+            chore_name: task.name.clone(),
+            entries: Vec::new(),
+            selected: 0,
+            loading: true,
+        };
+        self.view = View::History;
+        Some(self.history.chore_id)
+    }
+
+    pub fn close_history(&mut self) {
+        self.view = View::TaskList;
+    }
+
+    pub fn show_help(&mut self) {
+        self.help_scroll = 0;
+        self.view = View::Help;
     }
 
     pub fn show_add_form(&mut self) {
@@ -68,19 +848,69 @@ impl App {
         self.form_state = FormState::new();
     }
 
+    pub fn show_edit_form(&mut self) {
+        if let Some(task) = self.select_task() {
+            self.form_state = FormState::from_chore(task);
+            self.view = View::EditTask;
+        }
+    }
+
     pub fn show_task_list(&mut self) {
         self.view = View::TaskList;
         self.loading = true;
     }
 
+    /// Leaves the add or edit form without saving; nothing needs reloading.
+    pub fn close_form(&mut self) {
+        self.view = View::TaskList;
+    }
+
     pub fn show_error(&mut self, message: String) {
         self.error_message = Some(message);
+        self.error_hint = None;
         self.view = View::ErrorDialog;
         self.loading = false;
     }
 
+    /// Shows an API failure along with what the user can do about it.
+    pub fn show_api_error(&mut self, error: &ApiError) {
+        self.show_error(error.to_string());
+        self.error_hint = error.hint().map(str::to_string);
+    }
+
+    pub fn confirm(&mut self, message: String, action: ConfirmAction) {
+        self.confirmation = Some(Confirmation { message, action });
+        self.view = View::Confirm;
+    }
+
+    pub fn confirm_delete(&mut self) {
+        if let Some(task) = self.select_task() {
+            let message = format!("Delete \"{}\"?", task.name);
+            let action = ConfirmAction::DeleteTask(task.id);
+            self.confirm(message, action);
+        }
+    }
+
+    pub fn confirm_archive(&mut self) {
+        if let Some(task) = self.select_task() {
+            // Chores without an explicit flag are treated as active
+            let active = task.is_active.unwrap_or(true);
+            let verb = if active { "Archive" } else { "Restore" };
+            let message = format!("{} \"{}\"?", verb, task.name);
+            let action = ConfirmAction::SetTaskActive(task.id, !active);
+            self.confirm(message, action);
+        }
+    }
+
+    /// Dismisses the confirmation prompt, returning its action.
+    pub fn take_confirmation(&mut self) -> Option<ConfirmAction> {
+        self.view = View::TaskList;
+        self.confirmation.take().map(|c| c.action)
+    }
+
     pub fn clear_error(&mut self) {
         self.error_message = None;
+        self.error_hint = None;
         self.view = View::TaskList;
     }
 
@@ -89,95 +919,270 @@ impl App {
     }
 }
 
+impl Default for HistoryState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HistoryState {
+    pub fn new() -> Self {
+        Self {
+            chore_id: 0,
+            chore_name: String::new(),
+            entries: Vec::new(),
+            selected: 0,
+            loading: false,
+        }
+    }
+
+    pub fn next(&mut self) {
+        if self.selected + 1 < self.entries.len() {
+            self.selected += 1;
+        }
+    }
+
+    pub fn previous(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+}
+
+impl ProfileState {
+    pub fn next(&mut self) {
+        if self.selected + 1 < self.names.len() {
+            self.selected += 1;
+        }
+    }
+
+    pub fn previous(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+}
+
+impl Default for FormState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl FormState {
     pub fn new() -> Self {
         Self {
+            editing_id: None,
             name: String::new(),
             due_date: String::new(),
+            description: String::new(),
+            priority: String::new(),
+            labels: String::new(),
+            recurrence: String::new(),
+            rolling: false,
             cursor_position: 0,
             active_field: FormField::Name,
         }
     }
 
-    pub fn insert_char(&mut self, c: char) {
-        match self.active_field {
-            FormField::Name => {
-                self.name.insert(self.cursor_position, c);
-                self.cursor_position += 1;
+    pub fn from_chore(chore: &Chore) -> Self {
+        let name = chore.name.clone();
+        Self {
+            editing_id: Some(chore.id),
+            cursor_position: name.len(),
+            name,
+            due_date: chore
+                .next_due_date
+                .map(dates::format_due_input)
+                .unwrap_or_default(),
+            description: chore.description.clone().unwrap_or_default(),
+            priority: chore.priority.map(|p| p.to_string()).unwrap_or_default(),
+            labels: chore
+                .labels_v2
+                .as_ref()
+                .map(|l| l.join(", "))
+                .unwrap_or_default(),
+            recurrence: Recurrence::from_chore(chore)
+                .map(|r| r.to_input())
+                .unwrap_or_default(),
+            rolling: chore.is_rolling.unwrap_or(false),
+            active_field: FormField::Name,
+        }
+    }
+
+    pub fn field_value(&self, field: &FormField) -> &str {
+        match field {
+            FormField::Name => &self.name,
+            FormField::DueDate => &self.due_date,
+            FormField::Description => &self.description,
+            FormField::Priority => &self.priority,
+            FormField::Labels => &self.labels,
+            FormField::Recurrence => &self.recurrence,
+            FormField::Rolling => {
+                if self.rolling {
+                    "yes"
+                } else {
+                    "no"
+                }
             }
-            FormField::DueDate => {
-                self.due_date.insert(self.cursor_position, c);
-                self.cursor_position += 1;
+        }
+    }
+
+    /// The text being edited, or None for fields that are toggled instead.
+    fn active_value_mut(&mut self) -> Option<&mut String> {
+        let value = match self.active_field {
+            FormField::Name => &mut self.name,
+            FormField::DueDate => &mut self.due_date,
+            FormField::Description => &mut self.description,
+            FormField::Priority => &mut self.priority,
+            FormField::Labels => &mut self.labels,
+            FormField::Recurrence => &mut self.recurrence,
+            FormField::Rolling => return None,
+        };
+        Some(value)
+    }
+
+    pub fn insert_char(&mut self, c: char) {
+        if self.active_field == FormField::Rolling {
+            match c {
+                'y' | 'Y' => self.rolling = true,
+                'n' | 'N' => self.rolling = false,
+                ' ' => self.rolling = !self.rolling,
+                _ => {}
             }
+            return;
+        }
+
+        let position = self.cursor_position;
+        if let Some(value) = self.active_value_mut() {
+            value.insert(position, c);
+            self.cursor_position += c.len_utf8();
         }
     }
 
     pub fn delete_char(&mut self) {
         if self.cursor_position > 0 {
-            match self.active_field {
-                FormField::Name => {
-                    self.name.remove(self.cursor_position - 1);
-                }
-                FormField::DueDate => {
-                    self.due_date.remove(self.cursor_position - 1);
-                }
+            let position = self.cursor_position;
+            let Some(value) = self.active_value_mut() else {
+                return;
+            };
+            if let Some((index, _)) = value[..position].char_indices().next_back() {
+                value.remove(index);
+                self.cursor_position = index;
             }
-            self.cursor_position -= 1;
         }
     }
 
+    pub fn focus(&mut self, field: FormField) {
+        self.active_field = field;
+        self.cursor_position = self.field_value(&self.active_field).len();
+    }
+
     pub fn next_field(&mut self) {
-        match self.active_field {
-            FormField::Name => {
-                self.active_field = FormField::DueDate;
-                self.cursor_position = self.due_date.len();
-            }
-            FormField::DueDate => {
-                self.active_field = FormField::Name;
-                self.cursor_position = self.name.len();
-            }
-        }
+        self.focus(self.active_field.next());
     }
 
     pub fn previous_field(&mut self) {
-        self.next_field();
+        self.focus(self.active_field.previous());
     }
 
     pub fn is_valid(&self) -> bool {
-        // Only require name, due date is optional
-        if self.name.is_empty() {
+        // Only require name, everything else is optional
+        if self.name.trim().is_empty() {
             return false;
         }
 
         // If due date is provided, validate it
-        if !self.due_date.is_empty() {
-            return self.is_valid_date();
+        if !self.due_date.is_empty() && !self.is_valid_date() {
+            return false;
+        }
+
+        if !self.priority.is_empty() && self.parsed_priority().is_none() {
+            return false;
         }
 
-        true
+        self.recurrence.trim().is_empty() || self.parsed_recurrence().is_ok()
     }
 
-    fn is_valid_date(&self) -> bool {
-        if self.due_date.is_empty() {
-            return true; // Empty is valid (optional)
+    /// Builds the request for a new chore, omitting blank optional fields.
+    pub fn to_create_request(&self) -> CreateChoreRequest {
+        let recurrence = self.parsed_recurrence().ok();
+        CreateChoreRequest {
+            name: self.name.trim().to_string(),
+            due_date: self.parsed_due_date(),
+            description: non_empty(&self.description),
+            priority: self.parsed_priority(),
+            labels: self.parsed_labels(),
+            frequency_type: recurrence.as_ref().map(|r| r.frequency_type().to_string()),
+            frequency: recurrence.as_ref().map(|r| r.frequency()),
+            frequency_metadata: recurrence.as_ref().and_then(|r| r.metadata()),
+            is_rolling: Some(self.rolling),
         }
+    }
 
-        let parts: Vec<&str> = self.due_date.split('-').collect();
-        if parts.len() != 3 {
-            return false;
+    /// Builds an update for the chore being edited. Every field is sent so
+    /// clearing an input in the form clears it on the server too: a blank
+    /// due date is sent as null, and a blank priority as 0, which Donetick
+    /// treats as no priority.
+    pub fn to_update_request(&self) -> UpdateChoreRequest {
+        let recurrence = self.parsed_recurrence().ok();
+        UpdateChoreRequest {
+            name: Some(self.name.trim().to_string()),
+            due_date: Some(self.parsed_due_date()),
+            description: Some(self.description.trim().to_string()),
+            priority: Some(self.parsed_priority().unwrap_or(0)),
+            labels: Some(self.parsed_labels().unwrap_or_default()),
+            // A blank recurrence leaves the chore's schedule untouched
+            frequency_type: recurrence.as_ref().map(|r| r.frequency_type().to_string()),
+            frequency: recurrence.as_ref().map(|r| r.frequency()),
+            frequency_metadata: recurrence.as_ref().and_then(|r| r.metadata()),
+            is_rolling: Some(self.rolling),
+            assigned_to: None,
         }
+    }
 
-        let year = parts[0].parse::<i32>();
-        let month = parts[1].parse::<u32>();
-        let day = parts[2].parse::<u32>();
+    fn parsed_recurrence(&self) -> Result<Recurrence, String> {
+        Recurrence::parse(&self.recurrence)
+    }
 
-        if year.is_err() || month.is_err() || day.is_err() {
-            return false;
+    fn parsed_priority(&self) -> Option<i32> {
+        self.priority
+            .trim()
+            .parse::<i32>()
+            .ok()
+            .filter(|p| (0..=4).contains(p))
+    }
+
+    fn parsed_labels(&self) -> Option<Vec<String>> {
+        let labels: Vec<String> = self
+            .labels
+            .split(',')
+            .map(|l| l.trim())
+            .filter(|l| !l.is_empty())
+            .map(|l| l.to_string())
+            .collect();
+
+        if labels.is_empty() {
+            None
+        } else {
+            Some(labels)
+        }
+    }
+
+    fn is_valid_date(&self) -> bool {
+        if self.due_date.trim().is_empty() {
+            return true; // Empty is valid (optional)
         }
 
-        let month = month.unwrap();
-        let day = day.unwrap();
+        self.parsed_due_date().is_some()
+    }
+
+    fn parsed_due_date(&self) -> Option<DateTime<Utc>> {
+        dates::parse_due_input(&self.due_date)
+    }
+}
 
-        month >= 1 && month <= 12 && day >= 1 && day <= 31
+fn non_empty(value: &str) -> Option<String> {
+    let value = value.trim();
+    if value.is_empty() {
+        None
+    } else {
+        Some(value.to_string())
     }
 }
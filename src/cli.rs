@@ -0,0 +1,344 @@
+use crate::api::error::ApiError;
+use crate::api::{ApiClient, Chore, CreateChoreRequest};
+use crate::dates;
+use crate::filter::Filter;
+use crate::recurrence::{self, Recurrence};
+use crate::sort::{self, SortOrder};
+use chrono::Local;
+use clap::{Parser, Subcommand};
+use serde_json::json;
+use std::fmt;
+use std::process::ExitCode;
+
+/// Terminal UI for Donetick. Run without a subcommand to start the TUI.
+#[derive(Debug, Parser)]
+#[command(name = "donetick-tui", version, about)]
+pub struct Cli {
+    /// Print machine-readable JSON instead of text
+    #[arg(long, global = true)]
+    pub json: bool,
+
+    /// Config file profile to use instead of the default
+    #[arg(long, short = 'P', global = true, value_name = "NAME")]
+    pub profile: Option<String>,
+
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// List chores, soonest due first
+    List {
+        /// Only show chores matching a filter query, e.g. "label:kitchen due:<7d"
+        #[arg(long, short)]
+        filter: Option<String>,
+    },
+    /// Create a chore
+    Add {
+        name: String,
+        /// Due date, e.g. "tomorrow 9am", "next fri" or 2025-01-31
+        #[arg(long, short)]
+        due: Option<String>,
+        #[arg(long)]
+        description: Option<String>,
+        /// Priority from 0 (none) to 4
+        #[arg(long, short)]
+        priority: Option<i32>,
+        /// Comma separated labels
+        #[arg(long, short)]
+        labels: Option<String>,
+        /// Recurrence, e.g. "weekly", "every 3 days" or "mon wed fri"
+        #[arg(long, short)]
+        repeat: Option<String>,
+    },
+    /// Mark a chore as done
+    Complete { id: i64 },
+    /// Show every field of a chore
+    Show { id: i64 },
+}
+
+/// Why a subcommand failed. Each kind has its own exit code so scripts can
+/// tell a flaky network apart from a typo.
+#[derive(Debug)]
+pub enum CliError {
+    Api(ApiError),
+    Usage(String),
+    NotFound(i64),
+    /// The `--json` output could not be written
+    Output(serde_json::Error),
+}
+
+impl CliError {
+    pub fn exit_code(&self) -> u8 {
+        match self {
+            CliError::Usage(_) => 2,
+            CliError::Api(ApiError::Network(_) | ApiError::Timeout(_)) => 3,
+            CliError::Api(ApiError::ServerError(..)) => 4,
+            CliError::Api(ApiError::Decode { .. }) | CliError::Output(_) => 5,
+            CliError::Api(ApiError::InvalidConfig(_)) => 6,
+            CliError::Api(ApiError::NotFound(_)) | CliError::NotFound(_) => 7,
+            CliError::Api(ApiError::Unauthorized(_) | ApiError::Forbidden(_)) => 8,
+            CliError::Api(ApiError::Validation { .. }) => 9,
+            CliError::Api(ApiError::Conflict(_)) => 10,
+            CliError::Api(ApiError::RateLimited { .. }) => 11,
+        }
+    }
+}
+
+impl fmt::Display for CliError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CliError::Api(e) => write!(f, "{}", e),
+            CliError::Usage(msg) => write!(f, "{}", msg),
+            CliError::NotFound(id) => write!(f, "No chore with id {}", id),
+            CliError::Output(e) => write!(f, "Could not write JSON output: {}", e),
+        }
+    }
+}
+
+impl From<ApiError> for CliError {
+    fn from(err: ApiError) -> Self {
+        CliError::Api(err)
+    }
+}
+
+/// Runs a subcommand to completion, printing errors to stderr.
+pub async fn run(
+    command: Command,
+    client: &ApiClient,
+    current_user: Option<&str>,
+    json: bool,
+) -> ExitCode {
+    let result = match command {
+        Command::List { filter } => list(client, filter.as_deref(), current_user, json).await,
+        Command::Add {
+            name,
+            due,
+            description,
+            priority,
+            labels,
+            repeat,
+        } => match build_request(name, due, description, priority, labels, repeat) {
+            Ok(request) => add(client, request, json).await,
+            Err(e) => Err(e),
+        },
+        Command::Complete { id } => complete(client, id, json).await,
+        Command::Show { id } => show(client, id, json).await,
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            if let CliError::Api(api_error) = &e {
+                if let Some(hint) = api_error.hint() {
+                    eprintln!("hint: {}", hint);
+                }
+            }
+            ExitCode::from(e.exit_code())
+        }
+    }
+}
+
+async fn list(
+    client: &ApiClient,
+    filter: Option<&str>,
+    current_user: Option<&str>,
+    json: bool,
+) -> Result<(), CliError> {
+    let filter =
+        Filter::parse(filter.unwrap_or_default(), current_user).map_err(CliError::Usage)?;
+    let now = Local::now();
+
+    let mut chores: Vec<Chore> = client
+        .list_chores()
+        .await?
+        .into_iter()
+        .filter(|chore| filter.matches(chore, now))
+        .collect();
+    let order = SortOrder::default();
+    chores.sort_by(|a, b| order.compare(a, b));
+
+    if json {
+        print_json(&chores)?;
+    } else {
+        print_table(&chores);
+    }
+    Ok(())
+}
+
+fn build_request(
+    name: String,
+    due: Option<String>,
+    description: Option<String>,
+    priority: Option<i32>,
+    labels: Option<String>,
+    repeat: Option<String>,
+) -> Result<CreateChoreRequest, CliError> {
+    if name.trim().is_empty() {
+        return Err(CliError::Usage("Name cannot be empty".to_string()));
+    }
+    let due_date = due
+        .map(|due| {
+            dates::parse_due_input(&due).ok_or_else(|| {
+                CliError::Usage(format!("Could not understand due date \"{}\"", due))
+            })
+        })
+        .transpose()?;
+    if let Some(priority) = priority.filter(|p| !(0..=4).contains(p)) {
+        return Err(CliError::Usage(format!(
+            "Priority must be between 0 and 4, got {}",
+            priority
+        )));
+    }
+    let recurrence = repeat
+        .map(|repeat| Recurrence::parse(&repeat).map_err(CliError::Usage))
+        .transpose()?;
+    let labels = labels
+        .map(|labels| {
+            labels
+                .split(',')
+                .map(|l| l.trim().to_string())
+                .filter(|l| !l.is_empty())
+                .collect::<Vec<_>>()
+        })
+        .filter(|labels| !labels.is_empty());
+
+    Ok(CreateChoreRequest {
+        name: name.trim().to_string(),
+        due_date,
+        description: description.filter(|d| !d.trim().is_empty()),
+        priority,
+        labels,
+        frequency_type: recurrence.as_ref().map(|r| r.frequency_type().to_string()),
+        frequency: recurrence.as_ref().map(Recurrence::frequency),
+        frequency_metadata: recurrence.as_ref().and_then(Recurrence::metadata),
+        is_rolling: None,
+    })
+}
+
+async fn add(client: &ApiClient, request: CreateChoreRequest, json: bool) -> Result<(), CliError> {
+    let chore = client.create_chore(request).await?;
+    if json {
+        print_json(&chore)?;
+    } else {
+        println!("Created chore {}: {}", chore.id, chore.name);
+    }
+    Ok(())
+}
+
+async fn complete(client: &ApiClient, id: i64, json: bool) -> Result<(), CliError> {
+    client.complete_chore(id).await?;
+    if json {
+        print_json(&json!({ "id": id, "completed": true }))?;
+    } else {
+        println!("Completed chore {}", id);
+    }
+    Ok(())
+}
+
+async fn show(client: &ApiClient, id: i64, json: bool) -> Result<(), CliError> {
+    // There is no single-chore endpoint in the external API
+    let chore = client
+        .list_chores()
+        .await?
+        .into_iter()
+        .find(|chore| chore.id == id)
+        .ok_or(CliError::NotFound(id))?;
+
+    if json {
+        return print_json(&chore);
+    }
+
+    let now = Local::now();
+    let due = chore.next_due_date.map(|due| {
+        format!(
+            "{} ({})",
+            dates::format_local(due),
+            dates::relative_due(due, now)
+        )
+    });
+    let labels = chore
+        .labels_v2
+        .as_ref()
+        .filter(|l| !l.is_empty())
+        .map(|l| l.join(", "));
+    let fields = [
+        ("ID", Some(chore.id.to_string())),
+        ("Name", Some(chore.name.clone())),
+        ("Description", chore.description.clone()),
+        ("Status", Some(chore.status_name().to_string())),
+        ("Due", due),
+        ("Repeats", Some(recurrence::describe(&chore))),
+        ("Priority", chore.priority.map(|p| p.to_string())),
+        ("Labels", labels),
+        ("Assignee", sort::assignee_name(&chore)),
+        ("Created", chore.created_at.map(dates::format_local)),
+        ("Updated", chore.updated_at.map(dates::format_local)),
+    ];
+    for (label, value) in fields {
+        println!(
+            "{:<12} {}",
+            format!("{}:", label),
+            value.as_deref().unwrap_or("-")
+        );
+    }
+    Ok(())
+}
+
+fn print_json<T: serde::Serialize + ?Sized>(value: &T) -> Result<(), CliError> {
+    let text = serde_json::to_string_pretty(value).map_err(CliError::Output)?;
+    println!("{}", text);
+    Ok(())
+}
+
+/// Plain columns padded to the widest value, for reading or `awk`.
+fn print_table(chores: &[Chore]) {
+    const HEADERS: [&str; 7] = [
+        "ID", "NAME", "DUE", "REPEATS", "STATUS", "PRIORITY", "ASSIGNEE",
+    ];
+
+    let rows: Vec<[String; 7]> = chores
+        .iter()
+        .map(|chore| {
+            [
+                chore.id.to_string(),
+                chore.name.clone(),
+                chore
+                    .next_due_date
+                    .map(dates::format_local)
+                    .unwrap_or_else(|| "-".to_string()),
+                recurrence::describe(chore),
+                chore.status_name().to_string(),
+                chore
+                    .priority
+                    .map(|p| p.to_string())
+                    .unwrap_or_else(|| "-".to_string()),
+                sort::assignee_name(chore).unwrap_or_else(|| "-".to_string()),
+            ]
+        })
+        .collect();
+
+    let mut widths = HEADERS.map(str::len);
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let print_row = |cells: &[&str]| {
+        let line = cells
+            .iter()
+            .zip(widths)
+            .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+            .collect::<Vec<_>>()
+            .join("  ");
+        println!("{}", line.trim_end());
+    };
+
+    print_row(&HEADERS);
+    for row in &rows {
+        print_row(&row.each_ref().map(String::as_str));
+    }
+}
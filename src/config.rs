@@ -1,24 +1,226 @@
+use crate::api::ClientSettings;
+use crate::keymap::Keymap;
+use crate::theme::{self, StyleSpec, Theme};
 use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::collections::BTreeMap;
 use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+use std::time::Duration;
 
 #[derive(Debug, Clone)]
 pub struct Config {
+    /// Name of the profile from the config file, if one was used
+    pub profile: Option<String>,
     pub donetick_url: String,
     pub donetick_token: String,
+    /// Name or user id of the current user, used by `assignee:me` filters
+    pub donetick_user: Option<String>,
+    pub client_settings: ClientSettings,
+    /// How often the TUI reloads the list on its own; None turns it off
+    pub refresh_interval: Option<Duration>,
+    pub keymap: Keymap,
+    pub theme: Theme,
+}
+
+/// Reload period when the config does not set `refresh_interval`
+const DEFAULT_REFRESH: Duration = Duration::from_secs(60);
+
+/// Contents of `$XDG_CONFIG_HOME/donetick-tui/config.toml`.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ConfigFile {
+    default_profile: Option<String>,
+    /// Settings shared by every profile unless the profile overrides them
+    #[serde(default)]
+    defaults: Profile,
+    #[serde(default)]
+    profiles: BTreeMap<String, Profile>,
+    /// Key bindings by action name, replacing that action's defaults
+    #[serde(default)]
+    keys: BTreeMap<String, Keys>,
+    /// Built-in or user-defined theme; see `theme::default_name` when unset
+    theme: Option<String>,
+    /// User-defined themes by name
+    #[serde(default)]
+    themes: BTreeMap<String, ThemeFile>,
+}
+
+/// One binding (`delete = "dd"`) or several (`first = ["gg", "home"]`).
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum Keys {
+    One(String),
+    Many(Vec<String>),
+}
+
+/// A `[themes.<name>]` table: a built-in theme to start from, and styles
+/// by role replacing its colours.
+#[derive(Debug, Deserialize)]
+struct ThemeFile {
+    base: Option<String>,
+    #[serde(flatten)]
+    styles: BTreeMap<String, StyleEntry>,
+}
+
+/// Just a foreground colour (`overdue = "red"`) or a full style
+/// (`selected = { fg = "black", bg = "#eee8d5", modifiers = ["bold"] }`).
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum StyleEntry {
+    Color(String),
+    Style(StyleFile),
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct StyleFile {
+    fg: Option<String>,
+    bg: Option<String>,
+    #[serde(default)]
+    modifiers: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Profile {
+    url: Option<String>,
+    token: Option<String>,
+    /// Shell command that prints the token, e.g. `pass show donetick`
+    token_command: Option<String>,
+    user: Option<String>,
+    /// Seconds allowed for a whole request
+    timeout: Option<u64>,
+    /// Seconds allowed to connect
+    connect_timeout: Option<u64>,
+    /// Attempts per read request, including the first
+    max_attempts: Option<u32>,
+    /// Seconds between background reloads of the list; 0 turns them off
+    refresh_interval: Option<u64>,
+}
+
+impl Profile {
+    fn or(self, fallback: &Profile) -> Profile {
+        Profile {
+            url: self.url.or_else(|| fallback.url.clone()),
+            token: self.token.or_else(|| fallback.token.clone()),
+            token_command: self
+                .token_command
+                .or_else(|| fallback.token_command.clone()),
+            user: self.user.or_else(|| fallback.user.clone()),
+            timeout: self.timeout.or(fallback.timeout),
+            connect_timeout: self.connect_timeout.or(fallback.connect_timeout),
+            max_attempts: self.max_attempts.or(fallback.max_attempts),
+            refresh_interval: self.refresh_interval.or(fallback.refresh_interval),
+        }
+    }
+
+    fn client_settings(&self) -> ClientSettings {
+        let mut settings = ClientSettings::default();
+        if let Some(seconds) = self.timeout {
+            settings.timeout = Duration::from_secs(seconds);
+        }
+        if let Some(seconds) = self.connect_timeout {
+            settings.connect_timeout = Duration::from_secs(seconds);
+        }
+        if let Some(attempts) = self.max_attempts {
+            settings.retry.max_attempts = attempts.max(1);
+        }
+        settings
+    }
+
+    fn refresh_interval(&self) -> Option<Duration> {
+        match self.refresh_interval {
+            Some(0) => None,
+            Some(seconds) => Some(Duration::from_secs(seconds)),
+            None => Some(DEFAULT_REFRESH),
+        }
+    }
 }
 
 impl Config {
-    pub fn from_env() -> Result<Self> {
+    /// Loads the startup configuration: the named profile (or the file's
+    /// default), with `DONETICK_*` environment variables and `.env` applied
+    /// on top.
+    pub fn load(profile: Option<&str>) -> Result<Self> {
         dotenv::dotenv().ok();
 
-        let donetick_url = env::var("DONETICK_URL")
-            .context("DONETICK_URL environment variable not set")?;
-        let donetick_token = env::var("DONETICK_TOKEN")
-            .context("DONETICK_TOKEN environment variable not set")?;
+        let file = ConfigFile::load()?;
+        let requested = profile
+            .map(str::to_string)
+            .or_else(|| env_var("DONETICK_PROFILE"));
+        let name = requested.or_else(|| file.default_name());
+        let settings = match &name {
+            Some(name) => file.profile(name)?,
+            None => file.defaults.clone(),
+        };
+
+        let settings = Profile {
+            url: env_var("DONETICK_URL"),
+            token: env_var("DONETICK_TOKEN"),
+            user: env_var("DONETICK_USER"),
+            ..Profile::default()
+        }
+        .or(&settings);
+
+        Self::resolve(name, settings, &file)
+    }
+
+    /// Loads a profile exactly as written in the config file, ignoring the
+    /// environment. Used when switching profiles from inside the TUI.
+    pub fn for_profile(name: &str) -> Result<Self> {
+        let file = ConfigFile::load()?;
+        let settings = file.profile(name)?;
+        Self::resolve(Some(name.to_string()), settings, &file)
+    }
+
+    /// Names of the profiles in the config file, sorted.
+    pub fn profile_names() -> Vec<String> {
+        ConfigFile::load()
+            .map(|file| file.profiles.into_keys().collect())
+            .unwrap_or_default()
+    }
+
+    pub fn path() -> Option<PathBuf> {
+        Some(dirs::config_dir()?.join("donetick-tui").join("config.toml"))
+    }
+
+    fn resolve(profile: Option<String>, settings: Profile, file: &ConfigFile) -> Result<Self> {
+        let keymap = file.keymap()?;
+        let theme = file.theme()?;
+        let client_settings = settings.client_settings();
+        let refresh_interval = settings.refresh_interval();
+        let source = match &profile {
+            Some(name) => format!("profile \"{}\"", name),
+            None => "DONETICK_URL".to_string(),
+        };
+        let donetick_url = settings.url.with_context(|| {
+            format!(
+                "No server URL configured: set DONETICK_URL or add a profile to {}",
+                display_path()
+            )
+        })?;
+        let donetick_token = match (settings.token, settings.token_command) {
+            (Some(token), _) => token,
+            (None, Some(command)) => run_token_command(&command)
+                .with_context(|| format!("Failed to get the token for {}", source))?,
+            (None, None) => anyhow::bail!(
+                "No token configured for {}: set DONETICK_TOKEN, token or token_command",
+                source
+            ),
+        };
 
         let config = Self {
-            donetick_url,
+            profile,
+            donetick_url: donetick_url.trim_end_matches('/').to_string(),
             donetick_token,
+            donetick_user: settings.user.filter(|u| !u.is_empty()),
+            client_settings,
+            refresh_interval,
+            keymap,
+            theme,
         };
 
         config.validate()?;
@@ -38,3 +240,135 @@ impl Config {
         Ok(())
     }
 }
+
+impl ConfigFile {
+    /// A missing file is the same as an empty one; a malformed file is an
+    /// error so typos do not silently fall back to other settings.
+    fn load() -> Result<Self> {
+        let Some(path) = Config::path() else {
+            return Ok(Self::default());
+        };
+        let text = match fs::read_to_string(&path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(e) => return Err(e).with_context(|| format!("Failed to read {}", path.display())),
+        };
+        toml::from_str(&text).with_context(|| format!("Invalid config file {}", path.display()))
+    }
+
+    /// The configured default, or the only profile when there is just one.
+    fn default_name(&self) -> Option<String> {
+        if self.default_profile.is_some() {
+            return self.default_profile.clone();
+        }
+        match self.profiles.keys().collect::<Vec<_>>().as_slice() {
+            [only] => Some(only.to_string()),
+            _ => None,
+        }
+    }
+
+    fn keymap(&self) -> Result<Keymap> {
+        let keys = self
+            .keys
+            .iter()
+            .map(|(action, keys)| {
+                let keys = match keys {
+                    Keys::One(key) => vec![key.clone()],
+                    Keys::Many(keys) => keys.clone(),
+                };
+                (action.clone(), keys)
+            })
+            .collect();
+        Keymap::with_overrides(&keys)
+            .map_err(|e| anyhow::anyhow!("Invalid [keys] in {}: {}", display_path(), e))
+    }
+
+    /// The chosen theme, built in or from `[themes]`. A user-defined theme
+    /// with the name of a built-in one adjusts it; other names start from
+    /// `base`, or `dark` without one.
+    fn theme(&self) -> Result<Theme> {
+        let name = self.theme.as_deref().unwrap_or(theme::default_name());
+        let custom = self.themes.get(name);
+        let base = match custom.and_then(|t| t.base.as_deref()) {
+            Some(base) => base,
+            None if custom.is_some() && Theme::built_in(name).is_none() => "dark",
+            None => name,
+        };
+        let invalid = |e: String| anyhow::anyhow!("Invalid theme in {}: {}", display_path(), e);
+        let Some(theme) = Theme::built_in(base) else {
+            return Err(invalid(format!(
+                "Unknown theme \"{}\"; expected one of {} or a [themes] table",
+                base,
+                Theme::NAMES.join(", ")
+            )));
+        };
+        let Some(custom) = custom else {
+            return Ok(theme);
+        };
+        let styles = custom
+            .styles
+            .iter()
+            .map(|(role, style)| {
+                let spec = match style {
+                    StyleEntry::Color(fg) => StyleSpec {
+                        fg: Some(fg.clone()),
+                        ..StyleSpec::default()
+                    },
+                    StyleEntry::Style(style) => StyleSpec {
+                        fg: style.fg.clone(),
+                        bg: style.bg.clone(),
+                        modifiers: style.modifiers.clone(),
+                    },
+                };
+                (role.clone(), spec)
+            })
+            .collect();
+        theme.with_overrides(&styles).map_err(invalid)
+    }
+
+    fn profile(&self, name: &str) -> Result<Profile> {
+        let profile = self.profiles.get(name).cloned().with_context(|| {
+            let known: Vec<&str> = self.profiles.keys().map(String::as_str).collect();
+            if known.is_empty() {
+                format!(
+                    "Unknown profile \"{}\": no profiles in {}",
+                    name,
+                    display_path()
+                )
+            } else {
+                format!("Unknown profile \"{}\" (known: {})", name, known.join(", "))
+            }
+        })?;
+        Ok(profile.or(&self.defaults))
+    }
+}
+
+fn run_token_command(command: &str) -> Result<String> {
+    let output = Command::new("sh")
+        .arg("-c")
+        .arg(command)
+        .output()
+        .with_context(|| format!("Failed to run `{}`", command))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        anyhow::bail!("`{}` failed ({}) {}", command, output.status, stderr.trim());
+    }
+    let token = String::from_utf8(output.stdout)
+        .context("Token command printed invalid UTF-8")?
+        .trim()
+        .to_string();
+    if token.is_empty() {
+        anyhow::bail!("`{}` printed an empty token", command);
+    }
+    Ok(token)
+}
+
+fn env_var(name: &str) -> Option<String> {
+    env::var(name).ok().filter(|value| !value.is_empty())
+}
+
+fn display_path() -> String {
+    Config::path()
+        .map(|path| path.display().to_string())
+        .unwrap_or_else(|| "config.toml".to_string())
+}
@@ -0,0 +1,274 @@
+use crate::api::Chore;
+use chrono::{
+    DateTime, Datelike, Days, Duration, Local, Months, NaiveDate, NaiveDateTime, NaiveTime, Utc,
+    Weekday,
+};
+
+const DATE_FORMAT: &str = "%Y-%m-%d";
+const DATE_TIME_FORMAT: &str = "%Y-%m-%d %H:%M";
+
+/// Parses a due date typed into the form as local time.
+///
+/// Understands natural phrases such as "tomorrow 9am", "next fri",
+/// "in 3 days", "in 2h" and "eom", falling back to the strict
+/// `YYYY-MM-DD` / `YYYY-MM-DD HH:MM` formats. Dates that do not exist,
+/// such as 2025-02-31, are rejected.
+pub fn parse_due_input(input: &str) -> Option<DateTime<Utc>> {
+    parse_due_input_at(input, Local::now())
+}
+
+/// `parse_due_input` relative to a fixed `now`.
+pub fn parse_due_input_at(input: &str, now: DateTime<Local>) -> Option<DateTime<Utc>> {
+    let input = input.trim().to_lowercase();
+    let local = parse_strict(&input).or_else(|| parse_natural(&input, now))?;
+    Some(local.with_timezone(&Utc))
+}
+
+fn parse_strict(input: &str) -> Option<DateTime<Local>> {
+    let naive = NaiveDateTime::parse_from_str(input, DATE_TIME_FORMAT)
+        .ok()
+        .or_else(|| {
+            NaiveDate::parse_from_str(input, DATE_FORMAT)
+                .ok()?
+                .and_hms_opt(0, 0, 0)
+        })?;
+    naive.and_local_timezone(Local).earliest()
+}
+
+fn parse_natural(input: &str, now: DateTime<Local>) -> Option<DateTime<Local>> {
+    let tokens = tokenize(input);
+    let mut tokens: &[String] = &tokens;
+    let today = now.date_naive();
+
+    // "in 2h" and "in 30 minutes" are exact offsets from now
+    if let Some(offset) = parse_offset(tokens) {
+        return Some(now + offset);
+    }
+
+    let date = match tokens {
+        [first, amount, unit, rest @ ..] if first == "in" && amount.parse::<u32>().is_ok() => {
+            tokens = rest;
+            let amount = amount.parse::<u32>().ok()?;
+            add_calendar(today, amount, unit)?
+        }
+        [first, compact, rest @ ..] if first == "in" => {
+            tokens = rest;
+            let (amount, unit) = split_compact(compact)?;
+            add_calendar(today, amount, unit)?
+        }
+        [first, second, rest @ ..] if first == "next" => {
+            tokens = rest;
+            match second.as_str() {
+                "week" => today + Duration::days(7 - today.weekday().num_days_from_monday() as i64),
+                "month" => first_of_next_month(today)?,
+                weekday => next_weekday(today, parse_weekday(weekday)?),
+            }
+        }
+        [first, rest @ ..] if parse_date_word(first, today).is_some() => {
+            tokens = rest;
+            parse_date_word(first, today)?
+        }
+        _ => {
+            // A bare time means the next time the clock shows it
+            let time = parse_time_tokens(tokens)?;
+            let candidate = local_at(today, time)?;
+            return if candidate > now {
+                Some(candidate)
+            } else {
+                local_at(today.succ_opt()?, time)
+            };
+        }
+    };
+
+    let time = if tokens.is_empty() {
+        NaiveTime::MIN
+    } else {
+        parse_time_tokens(tokens)?
+    };
+    local_at(date, time)
+}
+
+/// Splits on whitespace, dropping filler words and joining "9 am" into "9am".
+fn tokenize(input: &str) -> Vec<String> {
+    let mut tokens: Vec<String> = Vec::new();
+    for word in input.split_whitespace() {
+        match word {
+            "at" | "on" => {}
+            "am" | "pm"
+                if tokens
+                    .last()
+                    .is_some_and(|t| t.chars().all(|c| c.is_ascii_digit() || c == ':')) =>
+            {
+                if let Some(last) = tokens.last_mut() {
+                    last.push_str(word);
+                }
+            }
+            _ => tokens.push(word.to_string()),
+        }
+    }
+    tokens
+}
+
+fn parse_offset(tokens: &[String]) -> Option<Duration> {
+    let (amount, unit) = match tokens {
+        [first, amount, unit] if first == "in" => (amount.parse::<i64>().ok()?, unit.as_str()),
+        [first, compact] if first == "in" => {
+            let (amount, unit) = split_compact(compact)?;
+            (amount as i64, unit)
+        }
+        _ => return None,
+    };
+    match unit {
+        "m" | "min" | "mins" | "minute" | "minutes" => Some(Duration::minutes(amount)),
+        "h" | "hr" | "hrs" | "hour" | "hours" => Some(Duration::hours(amount)),
+        _ => None,
+    }
+}
+
+/// "3d" -> (3, "d")
+fn split_compact(token: &str) -> Option<(u32, &str)> {
+    let split = token.find(|c: char| !c.is_ascii_digit())?;
+    let amount = token[..split].parse().ok()?;
+    Some((amount, &token[split..]))
+}
+
+fn add_calendar(date: NaiveDate, amount: u32, unit: &str) -> Option<NaiveDate> {
+    match unit {
+        "d" | "day" | "days" => date.checked_add_days(Days::new(amount as u64)),
+        "w" | "wk" | "week" | "weeks" => date.checked_add_days(Days::new(amount as u64 * 7)),
+        "mo" | "month" | "months" => date.checked_add_months(Months::new(amount)),
+        "y" | "year" | "years" => date.checked_add_months(Months::new(amount * 12)),
+        _ => None,
+    }
+}
+
+fn parse_date_word(word: &str, today: NaiveDate) -> Option<NaiveDate> {
+    match word {
+        "today" | "tod" => Some(today),
+        "tomorrow" | "tmr" | "tom" => today.succ_opt(),
+        "eow" => Some(today + Duration::days(6 - today.weekday().num_days_from_monday() as i64)),
+        "eom" => first_of_next_month(today)?.pred_opt(),
+        "eoy" => NaiveDate::from_ymd_opt(today.year(), 12, 31),
+        _ => match parse_weekday(word) {
+            Some(weekday) => Some(next_weekday(today, weekday)),
+            None => NaiveDate::parse_from_str(word, DATE_FORMAT).ok(),
+        },
+    }
+}
+
+fn parse_weekday(word: &str) -> Option<Weekday> {
+    match word {
+        "tues" => Some(Weekday::Tue),
+        "thur" | "thurs" => Some(Weekday::Thu),
+        _ => word.parse::<Weekday>().ok(),
+    }
+}
+
+/// The first `weekday` strictly after `today`.
+fn next_weekday(today: NaiveDate, weekday: Weekday) -> NaiveDate {
+    let ahead = (weekday.num_days_from_monday() + 7 - today.weekday().num_days_from_monday()) % 7;
+    let ahead = if ahead == 0 { 7 } else { ahead };
+    today + Duration::days(ahead as i64)
+}
+
+fn first_of_next_month(date: NaiveDate) -> Option<NaiveDate> {
+    date.with_day(1)?.checked_add_months(Months::new(1))
+}
+
+fn parse_time_tokens(tokens: &[String]) -> Option<NaiveTime> {
+    match tokens {
+        [time] => parse_time(time),
+        _ => None,
+    }
+}
+
+/// Accepts "9am", "9:30pm", "17:00", "noon" and "midnight".
+fn parse_time(token: &str) -> Option<NaiveTime> {
+    match token {
+        "noon" => return NaiveTime::from_hms_opt(12, 0, 0),
+        "midnight" => return Some(NaiveTime::MIN),
+        _ => {}
+    }
+
+    let (clock, meridiem) = if let Some(clock) = token.strip_suffix("am") {
+        (clock, Some(false))
+    } else if let Some(clock) = token.strip_suffix("pm") {
+        (clock, Some(true))
+    } else {
+        (token, None)
+    };
+
+    let (hour, minute) = match clock.split_once(':') {
+        Some((hour, minute)) => (hour.parse::<u32>().ok()?, minute.parse::<u32>().ok()?),
+        None if meridiem.is_some() => (clock.parse::<u32>().ok()?, 0),
+        None => return None,
+    };
+
+    let hour = match meridiem {
+        Some(_) if !(1..=12).contains(&hour) => return None,
+        Some(pm) => hour % 12 + if pm { 12 } else { 0 },
+        None => hour,
+    };
+    NaiveTime::from_hms_opt(hour, minute, 0)
+}
+
+fn local_at(date: NaiveDate, time: NaiveTime) -> Option<DateTime<Local>> {
+    date.and_time(time).and_local_timezone(Local).earliest()
+}
+
+/// Formats a due date the way `parse_due_input` reads it back, dropping the
+/// time when it is local midnight.
+pub fn format_due_input(due: DateTime<Utc>) -> String {
+    let local = due.with_timezone(&Local);
+    if local.time() == NaiveTime::MIN {
+        local.format(DATE_FORMAT).to_string()
+    } else {
+        local.format(DATE_TIME_FORMAT).to_string()
+    }
+}
+
+/// Absolute local timestamp for detail views.
+pub fn format_local(at: DateTime<Utc>) -> String {
+    at.with_timezone(&Local)
+        .format(DATE_TIME_FORMAT)
+        .to_string()
+}
+
+pub fn is_overdue(chore: &Chore, now: DateTime<Local>) -> bool {
+    chore.is_active != Some(false) && chore.next_due_date.is_some_and(|due| due < now)
+}
+
+/// Describes a due date relative to `now`, e.g. "in 2 days" or "overdue 3h".
+/// Dates more than a week out are shown as the local date instead.
+pub fn relative_due(due: DateTime<Utc>, now: DateTime<Local>) -> String {
+    let delta = due.with_timezone(&Local) - now;
+    let minutes = delta.num_minutes();
+
+    // Checked before truncating to minutes, so under a minute late is still overdue
+    if delta < Duration::zero() {
+        return format!("overdue {}", short_span(-minutes));
+    }
+    if minutes < 1 {
+        return "now".to_string();
+    }
+    if delta.num_days() >= 7 {
+        return due.with_timezone(&Local).format(DATE_FORMAT).to_string();
+    }
+    if minutes < 60 * 24 {
+        return format!("in {}", short_span(minutes));
+    }
+    match delta.num_days() {
+        1 => "in 1 day".to_string(),
+        days => format!("in {} days", days),
+    }
+}
+
+fn short_span(minutes: i64) -> String {
+    if minutes < 60 {
+        format!("{}m", minutes.max(1))
+    } else if minutes < 60 * 24 {
+        format!("{}h", minutes / 60)
+    } else {
+        format!("{}d", minutes / (60 * 24))
+    }
+}
@@ -0,0 +1,57 @@
+use crate::api::Chore;
+use std::collections::HashMap;
+
+/// How a chore differs between two loads of the list.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Change {
+    Added,
+    Changed,
+    Removed,
+}
+
+/// Compares two lists by chore id. Chores only known locally (negative ids
+/// for queued offline creates) are ignored. Results follow the order of
+/// `new`, then removed chores in the order of `old`.
+pub fn diff(old: &[Chore], new: &[Chore]) -> Vec<(i64, Change)> {
+    let before: HashMap<i64, &Chore> = old
+        .iter()
+        .filter(|c| c.id >= 0)
+        .map(|c| (c.id, c))
+        .collect();
+    let after: HashMap<i64, &Chore> = new
+        .iter()
+        .filter(|c| c.id >= 0)
+        .map(|c| (c.id, c))
+        .collect();
+
+    let updated =
+        new.iter()
+            .filter(|c| c.id >= 0)
+            .filter_map(|chore| match before.get(&chore.id) {
+                None => Some((chore.id, Change::Added)),
+                Some(previous) if *previous != chore => Some((chore.id, Change::Changed)),
+                Some(_) => None,
+            });
+    let removed = old
+        .iter()
+        .filter(|c| c.id >= 0 && !after.contains_key(&c.id))
+        .map(|c| (c.id, Change::Removed));
+    updated.chain(removed).collect()
+}
+
+/// A one-line summary such as "1 added, 2 changed", or None when nothing
+/// changed.
+pub fn summary(changes: &[(i64, Change)]) -> Option<String> {
+    let count = |kind: Change| changes.iter().filter(|(_, c)| *c == kind).count();
+    let parts: Vec<String> = [
+        (Change::Added, "added"),
+        (Change::Changed, "changed"),
+        (Change::Removed, "removed"),
+    ]
+    .into_iter()
+    .map(|(kind, label)| (count(kind), label))
+    .filter(|(n, _)| *n > 0)
+    .map(|(n, label)| format!("{} {}", n, label))
+    .collect();
+    (!parts.is_empty()).then(|| parts.join(", "))
+}
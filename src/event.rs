@@ -1,89 +1,483 @@
-use crate::api::{ApiClient, Chore};
-use crate::app::{App, View};
-use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyModifiers};
+use crate::api::error::ApiError;
+use crate::api::{ApiClient, Chore, ChoreApi, ChoreHistory};
+use crate::app::{App, BulkField, ConfirmAction, View};
+use crate::bulk::{self, BulkAction, BulkResult, LabelEdit};
+use crate::config::Config;
+use crate::dates;
+use crate::keymap::{Action, Context, KeyPress, Lookup};
+use crate::offline::{self, Operation, Outcome};
+use crate::undo::{self, Undo};
+use crossterm::event::{
+    Event, EventStream, KeyCode, KeyEvent, KeyEventKind, MouseButton, MouseEvent, MouseEventKind,
+};
+use futures::StreamExt;
+use ratatui::layout::Position;
+use std::future::Future;
+use std::time::Duration;
 use tokio::sync::mpsc;
+use tokio_util::sync::CancellationToken;
 
 #[derive(Debug)]
 pub enum AppEvent {
     Input(KeyEvent),
+    Mouse(MouseEvent),
+    /// The terminal was resized to width, height
+    Resize(u16, u16),
+    FocusGained,
+    Paste(String),
+    /// Reading terminal input failed; the app cannot carry on without it
+    InputFailed(String),
+    /// Sent every `TICK`; drives the periodic refresh and fades highlights
+    Tick,
     TasksLoaded(Vec<Chore>),
-    TaskAdded,
-    TaskCompleted,
+    /// The periodic refresh's list, whose changes are highlighted since they
+    /// were made elsewhere
+    TasksRefreshed(Vec<Chore>),
+    /// A background refresh failed for a reason other than being offline
+    RefreshFailed(ApiError),
+    /// The server could not be reached while loading tasks
+    Offline(String),
+    RetryLoad,
+    QueueOffline(Operation),
+    OutboxReplayed(Vec<(u64, Outcome)>),
+    HistoryLoaded(i64, Vec<ChoreHistory>),
+    /// A read request failed and is about to be retried: attempt, limit
+    Retrying(u32, u32),
+    /// The profile switcher resolved a new server configuration
+    ProfileLoaded(Box<Config>),
+    /// A change went through; the `Undo` takes it back
+    TaskAdded(Undo),
+    TaskUpdated(Undo),
+    TaskCompleted(Undo),
+    TaskDeleted(Undo),
+    TaskArchived(Undo),
+    /// `u` reverted this change
+    Undone(Undo),
+    /// `u` could not revert these; they go back on the undo stack
+    UndoFailed(Undo, ApiError),
+    /// A bulk action finished another chore: done, total
+    BulkProgress(usize, usize),
+    BulkFinished(BulkAction, Vec<BulkResult>),
+    ApiError(ApiError),
     Error(String),
 }
 
+impl AppEvent {
+    /// Whether the event reports on work done against one profile's server,
+    /// and so means nothing once another profile is open.
+    pub fn is_profile_bound(&self) -> bool {
+        matches!(
+            self,
+            AppEvent::TasksLoaded(_)
+                | AppEvent::TasksRefreshed(_)
+                | AppEvent::RefreshFailed(_)
+                | AppEvent::Offline(_)
+                | AppEvent::QueueOffline(_)
+                | AppEvent::OutboxReplayed(_)
+                | AppEvent::HistoryLoaded(..)
+                | AppEvent::Retrying(..)
+                | AppEvent::TaskAdded(_)
+                | AppEvent::TaskUpdated(_)
+                | AppEvent::TaskCompleted(_)
+                | AppEvent::TaskDeleted(_)
+                | AppEvent::TaskArchived(_)
+                | AppEvent::Undone(_)
+                | AppEvent::UndoFailed(..)
+                | AppEvent::BulkProgress(..)
+                | AppEvent::BulkFinished(..)
+                | AppEvent::ApiError(_)
+        )
+    }
+}
+
+/// The receiving end of the app's events, owned by the main loop.
 pub struct EventHandler {
-    tx: mpsc::UnboundedSender<AppEvent>,
-    rx: mpsc::UnboundedReceiver<AppEvent>,
+    tx: EventSender,
+    rx: mpsc::UnboundedReceiver<(u64, AppEvent)>,
+}
+
+impl Default for EventHandler {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl EventHandler {
     pub fn new() -> Self {
         let (tx, rx) = mpsc::unbounded_channel();
+        let tx = EventSender {
+            tx,
+            shutdown: CancellationToken::new(),
+            generation: 0,
+        };
         Self { tx, rx }
     }
 
-    pub fn sender(&self) -> mpsc::UnboundedSender<AppEvent> {
+    pub fn sender(&self) -> EventSender {
         self.tx.clone()
     }
 
+    /// The next event, whichever profile it was sent for.
     pub async fn next(&mut self) -> Option<AppEvent> {
-        self.rx.recv().await
+        self.rx.recv().await.map(|(_, event)| event)
+    }
+
+    /// The next event, skipping reports on work started for a profile
+    /// other than the one opened as `generation`.
+    pub async fn next_for(&mut self, generation: u64) -> Option<AppEvent> {
+        loop {
+            let (sent_for, event) = self.rx.recv().await?;
+            if sent_for == generation || !event.is_profile_bound() {
+                return Some(event);
+            }
+        }
+    }
+
+    /// Stops the input loop and every task started through
+    /// `EventSender::spawn`.
+    pub fn shutdown(&self) {
+        self.tx.shutdown.cancel();
+    }
+}
+
+/// Whichever way the main loop ends, nothing it started outlives it.
+impl Drop for EventHandler {
+    fn drop(&mut self) {
+        self.shutdown();
     }
 }
 
-pub async fn handle_key_event(
+/// Sends events to the main loop and starts the background work that
+/// produces them, all tied to one shutdown token. Events are tagged with the
+/// profile generation the sender was made for.
+#[derive(Clone)]
+pub struct EventSender {
+    tx: mpsc::UnboundedSender<(u64, AppEvent)>,
+    shutdown: CancellationToken,
+    generation: u64,
+}
+
+impl EventSender {
+    /// Returns false once the main loop has stopped listening.
+    pub fn send(&self, event: AppEvent) -> bool {
+        self.tx.send((self.generation, event)).is_ok()
+    }
+
+    /// A sender for work done against the profile opened as `generation`;
+    /// see `App::generation`.
+    pub fn for_generation(&self, generation: u64) -> EventSender {
+        EventSender {
+            generation,
+            ..self.clone()
+        }
+    }
+
+    /// Runs a task until it finishes or the app shuts down, whichever comes
+    /// first. The task gets its own sender to report back with.
+    pub fn spawn<F>(&self, task: impl FnOnce(EventSender) -> F)
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        let task = task(self.clone());
+        let shutdown = self.shutdown.clone();
+        tokio::spawn(async move {
+            tokio::select! {
+                _ = shutdown.cancelled() => {}
+                _ = task => {}
+            }
+        });
+    }
+}
+
+pub async fn handle_key_event<A: ChoreApi>(
     key: KeyEvent,
     app: &mut App,
-    client: &ApiClient,
-    tx: &mpsc::UnboundedSender<AppEvent>,
+    client: &A,
+    tx: &EventSender,
 ) {
+    // Status messages last until the next key press
+    app.status = None;
+
     match app.view {
-        View::TaskList => handle_task_list_input(key, app, client, tx).await,
-        View::AddTask => handle_form_input(key, app, client, tx).await,
-        View::ErrorDialog => handle_error_dialog_input(key, app),
+        View::ErrorDialog => return handle_error_dialog_input(key, app),
+        View::BulkSummary => return handle_bulk_summary_input(key, app),
+        _ => {}
+    }
+    match resolve_key(key, app) {
+        Resolved::Action(action) => handle_action(action, app, client, tx).await,
+        Resolved::Pending => {}
+        Resolved::Unbound => handle_typed_key(key, app, client, tx).await,
     }
 }
 
-async fn handle_task_list_input(
-    key: KeyEvent,
+enum Resolved {
+    Action(Action),
+    /// Part of a longer binding such as `gg`
+    Pending,
+    Unbound,
+}
+
+/// Adds `key` to the keys pressed so far and looks them up in the keymap.
+fn resolve_key(key: KeyEvent, app: &mut App) -> Resolved {
+    let context = key_context(app);
+    app.pending_keys.push(KeyPress::from(key));
+    match app.keymap.lookup(context, &app.pending_keys) {
+        Lookup::Action(action) => {
+            app.pending_keys.clear();
+            Resolved::Action(action)
+        }
+        Lookup::Pending => Resolved::Pending,
+        // A sequence that went nowhere is dropped and the key tried alone
+        Lookup::None if app.pending_keys.len() > 1 => {
+            app.pending_keys.clear();
+            resolve_key(key, app)
+        }
+        Lookup::None => {
+            app.pending_keys.clear();
+            Resolved::Unbound
+        }
+    }
+}
+
+/// Views that take text look keys up among the input bindings, so plain
+/// characters stay free for typing.
+pub fn key_context(app: &App) -> Context {
+    match app.view {
+        View::TaskList if app.search.editing || app.filter.editing => Context::Input,
+        View::AddTask | View::EditTask | View::Confirm | View::BulkEdit => Context::Input,
+        _ => Context::List,
+    }
+}
+
+/// Runs `action` in the current view. Clicked footer hints come here too.
+pub async fn handle_action<A: ChoreApi>(
+    action: Action,
     app: &mut App,
-    client: &ApiClient,
-    tx: &mpsc::UnboundedSender<AppEvent>,
+    client: &A,
+    tx: &EventSender,
 ) {
-    match key.code {
-        KeyCode::Char('q') => app.quit(),
-        KeyCode::Char('a') => app.show_add_form(),
-        KeyCode::Char('r') => {
-            app.loading = true;
-            let client = client.clone();
-            let tx = tx.clone();
-            tokio::spawn(async move {
-                match client.list_chores().await {
-                    Ok(tasks) => {
-                        let _ = tx.send(AppEvent::TasksLoaded(tasks));
-                    }
-                    Err(e) => {
-                        let _ = tx.send(AppEvent::Error(e.to_string()));
+    match app.view {
+        View::TaskList if app.search.editing => match action {
+            Action::Submit => app.finish_search(),
+            Action::Cancel => app.clear_search(),
+            _ => {}
+        },
+        View::TaskList if app.filter.editing => match action {
+            Action::Submit => app.finish_filter(),
+            Action::Cancel => app.clear_filter(),
+            _ => {}
+        },
+        View::TaskList => handle_task_list_action(action, app, client, tx).await,
+        View::AddTask | View::EditTask => handle_form_action(action, app, client, tx).await,
+        View::ErrorDialog => app.clear_error(),
+        View::Confirm => handle_confirm_action(action, app, client, tx).await,
+        View::History => handle_history_action(action, app),
+        View::Profiles => handle_profiles_action(action, app, tx),
+        View::BulkEdit => handle_bulk_edit_action(action, app, client, tx),
+        View::BulkSummary => app.view = View::TaskList,
+        View::Help => handle_help_action(action, app),
+    }
+}
+
+/// Keys no binding claimed: text for the inputs, and `y`/`n` in
+/// confirmations.
+async fn handle_typed_key<A: ChoreApi>(key: KeyEvent, app: &mut App, client: &A, tx: &EventSender) {
+    match app.view {
+        View::TaskList if app.search.editing => handle_search_input(key, app),
+        View::TaskList if app.filter.editing => handle_filter_input(key, app),
+        View::AddTask | View::EditTask => handle_form_input(key, app),
+        View::BulkEdit => handle_bulk_edit_input(key, app),
+        View::Confirm => match key.code {
+            KeyCode::Char('y') | KeyCode::Char('Y') => {
+                handle_confirm_action(Action::Submit, app, client, tx).await
+            }
+            KeyCode::Char('n') | KeyCode::Char('N') => {
+                handle_confirm_action(Action::Cancel, app, client, tx).await
+            }
+            _ => {}
+        },
+        _ => {}
+    }
+}
+
+/// Rows the list moves per notch of the mouse wheel
+const WHEEL_ROWS: isize = 3;
+
+/// Clicks act on whatever was drawn under the pointer in the last frame.
+/// Footer hints run their action, exactly as their keys would.
+pub async fn handle_mouse_event<A: ChoreApi>(
+    mouse: MouseEvent,
+    app: &mut App,
+    client: &A,
+    tx: &EventSender,
+) {
+    let at = Position::new(mouse.column, mouse.row);
+    match mouse.kind {
+        MouseEventKind::ScrollDown => scroll(app, WHEEL_ROWS),
+        MouseEventKind::ScrollUp => scroll(app, -WHEEL_ROWS),
+        MouseEventKind::Down(MouseButton::Left) => {
+            app.status = None;
+            let hint = app
+                .regions
+                .hints
+                .iter()
+                .find(|(region, _)| region.contains(at))
+                .map(|(_, action)| *action);
+            if let Some(action) = hint {
+                handle_action(action, app, client, tx).await;
+                return;
+            }
+
+            match app.view {
+                View::ErrorDialog => app.clear_error(),
+                View::BulkSummary => app.view = View::TaskList,
+                View::TaskList if app.regions.task_rows.contains(at) => {
+                    let row = (at.y - app.regions.task_rows.y) as usize;
+                    app.select_row(app.scroll_offset + row);
+                }
+                View::AddTask | View::EditTask => {
+                    let field = app
+                        .regions
+                        .form_fields
+                        .iter()
+                        .find(|(_, region)| region.contains(at))
+                        .map(|(field, _)| field.clone());
+                    if let Some(field) = field {
+                        app.form_state.focus(field);
                     }
                 }
-            });
+                _ => {}
+            }
+        }
+        _ => {}
+    }
+}
+
+/// The task list and help screen scroll; the history and profile lists
+/// move their selection one entry per notch.
+fn scroll(app: &mut App, rows: isize) {
+    match app.view {
+        View::TaskList => app.scroll_by(rows),
+        View::History if rows > 0 => app.history.next(),
+        View::History => app.history.previous(),
+        View::Profiles if rows > 0 => app.profiles.next(),
+        View::Profiles => app.profiles.previous(),
+        View::Help => app.help_scroll = app.help_scroll.saturating_add_signed(rows as i16),
+        _ => {}
+    }
+}
+
+async fn handle_task_list_action<A: ChoreApi>(
+    action: Action,
+    app: &mut App,
+    client: &A,
+    tx: &EventSender,
+) {
+    // Tasks created offline only exist locally until the outbox is replayed
+    let unsynced = app.select_task().is_some_and(|t| t.id < 0);
+
+    match action {
+        Action::Complete if app.has_marks() => spawn_bulk(app, client, tx, BulkAction::Complete),
+        Action::Delete if app.has_marks() => {
+            let count = app.bulk_targets().len();
+            app.confirm(
+                format!("Delete {} chores?", count),
+                ConfirmAction::BulkDelete,
+            );
+        }
+        Action::Cancel if app.has_marks() => app.clear_marks(),
+        Action::Mark => app.toggle_mark(),
+        Action::MarkRange => app.toggle_visual(),
+        Action::Relabel => app.start_bulk_edit(BulkField::Labels),
+        Action::Reassign => app.start_bulk_edit(BulkField::Assignee),
+        Action::Reschedule => app.start_bulk_edit(BulkField::DueDate),
+        Action::Complete | Action::Edit | Action::Delete | Action::Archive | Action::History
+            if unsynced =>
+        {
+            app.notify("This task has not been synced to the server yet".to_string());
+        }
+        Action::Quit => app.quit(),
+        Action::Help => app.show_help(),
+        Action::Undo => spawn_undo(app, client, tx),
+        Action::Search => app.start_search(),
+        Action::Cancel => app.clear_search(),
+        Action::Filter => app.start_filter(),
+        Action::ClearFilter => app.clear_filter(),
+        Action::Add => app.show_add_form(),
+        Action::Edit => app.show_edit_form(),
+        Action::Delete => app.confirm_delete(),
+        Action::Archive => app.confirm_archive(),
+        Action::Details => app.toggle_details(),
+        Action::Sort => {
+            app.cycle_sort_key();
+            save_state(app);
+        }
+        Action::ReverseSort => {
+            app.toggle_sort_direction();
+            save_state(app);
+        }
+        Action::History => {
+            if let Some(id) = app.show_history() {
+                let client = client.clone();
+                tx.spawn(|tx| async move {
+                    match client.chore_history(id).await {
+                        Ok(entries) => {
+                            tx.send(AppEvent::HistoryLoaded(id, entries));
+                        }
+                        Err(e) => {
+                            tx.send(AppEvent::ApiError(e));
+                        }
+                    }
+                });
+            }
         }
-        KeyCode::Down | KeyCode::Char('j') => app.next_task(),
-        KeyCode::Up | KeyCode::Char('k') => app.previous_task(),
-        KeyCode::Enter => {
+        Action::Refresh => {
+            app.loading = true;
+            spawn_load_tasks(client, tx);
+        }
+        Action::Profiles => {
+            let names = Config::profile_names();
+            if names.is_empty() {
+                let path = Config::path()
+                    .map(|p| p.display().to_string())
+                    .unwrap_or_else(|| "config.toml".to_string());
+                app.notify(format!("No profiles configured in {}", path));
+            } else {
+                app.show_profiles(names);
+            }
+        }
+        Action::MoveDown => app.next_task(),
+        Action::MoveUp => app.previous_task(),
+        Action::PageDown => app.page_down(),
+        Action::PageUp => app.page_up(),
+        Action::First => app.first_task(),
+        Action::Last => app.last_task(),
+        Action::Complete => {
             if let Some(task) = app.select_task() {
                 let task_id = task.id;
+                let undo = Undo::completed(task);
+                let operation = Operation::Complete {
+                    chore_id: task.id,
+                    name: task.name.clone(),
+                    due: task.next_due_date,
+                };
+                // Keep queued operations in order by queueing behind them
+                if !app.outbox.is_empty() {
+                    app.queue_offline(operation);
+                    return;
+                }
                 let client = client.clone();
-                let tx = tx.clone();
                 app.loading = true;
-                tokio::spawn(async move {
+                tx.spawn(|tx| async move {
                     match client.complete_chore(task_id).await {
                         Ok(_) => {
-                            let _ = tx.send(AppEvent::TaskCompleted);
+                            tx.send(AppEvent::TaskCompleted(undo));
+                        }
+                        Err(e) if e.is_offline() => {
+                            tx.send(AppEvent::QueueOffline(operation));
                         }
                         Err(e) => {
-                            let _ = tx.send(AppEvent::Error(e.to_string()));
+                            tx.send(AppEvent::ApiError(e));
                         }
                     }
                 });
@@ -93,42 +487,89 @@ async fn handle_task_list_input(
     }
 }
 
-async fn handle_form_input(
-    key: KeyEvent,
+fn handle_search_input(key: KeyEvent, app: &mut App) {
+    match key.code {
+        KeyCode::Backspace => app.pop_search_char(),
+        KeyCode::Down => app.next_task(),
+        KeyCode::Up => app.previous_task(),
+        KeyCode::PageDown => app.page_down(),
+        KeyCode::PageUp => app.page_up(),
+        KeyCode::Char(c) => app.push_search_char(c),
+        _ => {}
+    }
+}
+
+fn handle_filter_input(key: KeyEvent, app: &mut App) {
+    match key.code {
+        KeyCode::Backspace => app.pop_filter_char(),
+        KeyCode::Down => app.next_task(),
+        KeyCode::Up => app.previous_task(),
+        KeyCode::PageDown => app.page_down(),
+        KeyCode::PageUp => app.page_up(),
+        KeyCode::Char(c) => app.push_filter_char(c),
+        _ => {}
+    }
+}
+
+async fn handle_form_action<A: ChoreApi>(
+    action: Action,
     app: &mut App,
-    client: &ApiClient,
-    tx: &mpsc::UnboundedSender<AppEvent>,
+    client: &A,
+    tx: &EventSender,
 ) {
-    match key.code {
-        KeyCode::Esc => app.show_task_list(),
-        KeyCode::Tab => {
-            if key.modifiers.contains(KeyModifiers::SHIFT) {
-                app.form_state.previous_field();
-            } else {
-                app.form_state.next_field();
-            }
-        }
-        KeyCode::Enter => {
-            if app.form_state.is_valid() {
-                let name = app.form_state.name.clone();
-                let due_date = app.form_state.due_date.clone();
+    match action {
+        Action::Cancel => app.close_form(),
+        Action::NextField => app.form_state.next_field(),
+        Action::PreviousField => app.form_state.previous_field(),
+        Action::Submit => {
+            if !app.form_state.is_valid() {
+                app.show_error("Please fill in all fields with valid data".to_string());
+            } else if let Some(id) = app.form_state.editing_id {
+                let request = app.form_state.to_update_request();
+                let Some(undo) = app.task(id).map(Undo::edited) else {
+                    return;
+                };
                 let client = client.clone();
-                let tx = tx.clone();
                 app.loading = true;
-                tokio::spawn(async move {
-                    match client.create_chore(name, due_date).await {
+                tx.spawn(|tx| async move {
+                    match client.update_chore(id, request).await {
                         Ok(_) => {
-                            let _ = tx.send(AppEvent::TaskAdded);
+                            tx.send(AppEvent::TaskUpdated(undo));
                         }
                         Err(e) => {
-                            let _ = tx.send(AppEvent::Error(e.to_string()));
+                            tx.send(AppEvent::ApiError(e));
                         }
                     }
                 });
             } else {
-                app.show_error("Please fill in all fields with valid data".to_string());
+                let request = app.form_state.to_create_request();
+                if !app.outbox.is_empty() {
+                    app.queue_offline(Operation::Create { request });
+                    return;
+                }
+                let client = client.clone();
+                app.loading = true;
+                tx.spawn(|tx| async move {
+                    match client.create_chore(request.clone()).await {
+                        Ok(chore) => {
+                            tx.send(AppEvent::TaskAdded(Undo::created(&chore)));
+                        }
+                        Err(e) if e.is_offline() => {
+                            tx.send(AppEvent::QueueOffline(Operation::Create { request }));
+                        }
+                        Err(e) => {
+                            tx.send(AppEvent::ApiError(e));
+                        }
+                    }
+                });
             }
         }
+        _ => {}
+    }
+}
+
+fn handle_form_input(key: KeyEvent, app: &mut App) {
+    match key.code {
         KeyCode::Backspace => {
             app.form_state.delete_char();
         }
@@ -139,18 +580,370 @@ async fn handle_form_input(
     }
 }
 
+async fn handle_confirm_action<A: ChoreApi>(
+    action: Action,
+    app: &mut App,
+    client: &A,
+    tx: &EventSender,
+) {
+    match action {
+        Action::Submit => {
+            let client = client.clone();
+            match app.take_confirmation() {
+                Some(ConfirmAction::DeleteTask(id)) => {
+                    let Some(undo) = app.task(id).map(Undo::deleted) else {
+                        return;
+                    };
+                    spawn_mutation(app, tx, AppEvent::TaskDeleted(undo), async move {
+                        client.delete_chore(id).await
+                    });
+                }
+                Some(ConfirmAction::SetTaskActive(id, active)) => {
+                    let Some(undo) = app.task(id).map(|t| Undo::archived(t, active)) else {
+                        return;
+                    };
+                    spawn_mutation(app, tx, AppEvent::TaskArchived(undo), async move {
+                        client.set_chore_active(id, active).await
+                    });
+                }
+                Some(ConfirmAction::BulkDelete) => {
+                    spawn_bulk(app, &client, tx, BulkAction::Delete);
+                }
+                None => {}
+            }
+        }
+        Action::Cancel => {
+            app.take_confirmation();
+        }
+        _ => {}
+    }
+}
+
+/// Runs a single-chore request in the background, sending `done` when it
+/// succeeds.
+fn spawn_mutation(
+    app: &mut App,
+    tx: &EventSender,
+    done: AppEvent,
+    request: impl Future<Output = Result<(), ApiError>> + Send + 'static,
+) {
+    app.loading = true;
+    tx.spawn(|tx| async move {
+        match request.await {
+            Ok(()) => {
+                tx.send(done);
+            }
+            Err(e) => {
+                tx.send(AppEvent::ApiError(e));
+            }
+        }
+    });
+}
+
+/// Reverts the most recent change through the API.
+fn spawn_undo<A: ChoreApi>(app: &mut App, client: &A, tx: &EventSender) {
+    if app.offline {
+        app.notify("Undo needs a connection to the server".to_string());
+        return;
+    }
+    let Some(undo) = app.undo.pop() else {
+        app.notify("Nothing to undo".to_string());
+        return;
+    };
+    let client = client.clone();
+    app.loading = true;
+    tx.spawn(|tx| async move {
+        match undo::revert(&client, &undo).await {
+            Ok(()) => {
+                tx.send(AppEvent::Undone(undo));
+            }
+            Err((failed, e)) => {
+                tx.send(AppEvent::UndoFailed(failed, e));
+            }
+        }
+    });
+}
+
+fn handle_bulk_edit_input(key: KeyEvent, app: &mut App) {
+    match key.code {
+        KeyCode::Backspace => {
+            app.bulk_prompt.input.pop();
+            app.bulk_prompt.error = None;
+        }
+        KeyCode::Char(c) => {
+            app.bulk_prompt.input.push(c);
+            app.bulk_prompt.error = None;
+        }
+        _ => {}
+    }
+}
+
+fn handle_bulk_edit_action<A: ChoreApi>(
+    action: Action,
+    app: &mut App,
+    client: &A,
+    tx: &EventSender,
+) {
+    match action {
+        Action::Cancel => app.view = View::TaskList,
+        Action::Submit => {
+            let input = app.bulk_prompt.input.trim();
+            let action = match app.bulk_prompt.field {
+                BulkField::Labels => LabelEdit::parse(input).map(BulkAction::Relabel),
+                BulkField::Assignee => app
+                    .resolve_user(input)
+                    .map(BulkAction::Reassign)
+                    .ok_or_else(|| format!("No user with id or name \"{}\"", input)),
+                BulkField::DueDate => dates::parse_due_input(input)
+                    .map(BulkAction::Reschedule)
+                    .ok_or_else(|| format!("Could not understand due date \"{}\"", input)),
+            };
+            match action {
+                Ok(action) => {
+                    app.view = View::TaskList;
+                    spawn_bulk(app, client, tx, action);
+                }
+                Err(e) => app.bulk_prompt.error = Some(e),
+            }
+        }
+        _ => {}
+    }
+}
+
+fn handle_bulk_summary_input(key: KeyEvent, app: &mut App) {
+    if matches!(key.code, KeyCode::Char(_) | KeyCode::Enter | KeyCode::Esc) {
+        app.view = View::TaskList;
+    }
+}
+
+/// Applies `action` to the marked chores (or the selected one) in the
+/// background, reporting progress as it goes.
+pub fn spawn_bulk<A: ChoreApi>(app: &mut App, client: &A, tx: &EventSender, action: BulkAction) {
+    if app.bulk_progress.is_some() {
+        app.notify("Wait for the current bulk action to finish".to_string());
+        return;
+    }
+    if app.offline {
+        app.notify("Bulk actions need a connection to the server".to_string());
+        return;
+    }
+    let chores = app.bulk_targets();
+    if chores.is_empty() {
+        return;
+    }
+
+    app.begin_bulk(&action, chores.len());
+    let client = client.clone();
+    tx.spawn(|tx| async move {
+        let progress = tx.clone();
+        let results = bulk::run(&client, action.clone(), chores, move |done, total| {
+            let _ = progress.send(AppEvent::BulkProgress(done, total));
+        })
+        .await;
+        tx.send(AppEvent::BulkFinished(action, results));
+    });
+}
+
+fn handle_history_action(action: Action, app: &mut App) {
+    match action {
+        Action::Cancel | Action::Quit | Action::History => app.close_history(),
+        Action::MoveDown => app.history.next(),
+        Action::MoveUp => app.history.previous(),
+        _ => {}
+    }
+}
+
+/// Help lines scrolled per page key
+const HELP_PAGE: u16 = 10;
+
+fn handle_help_action(action: Action, app: &mut App) {
+    match action {
+        Action::Cancel | Action::Quit | Action::Help => app.view = View::TaskList,
+        Action::MoveDown => app.help_scroll = app.help_scroll.saturating_add(1),
+        Action::MoveUp => app.help_scroll = app.help_scroll.saturating_sub(1),
+        Action::PageDown => app.help_scroll = app.help_scroll.saturating_add(HELP_PAGE),
+        Action::PageUp => app.help_scroll = app.help_scroll.saturating_sub(HELP_PAGE),
+        Action::First => app.help_scroll = 0,
+        _ => {}
+    }
+}
+
+fn handle_profiles_action(action: Action, app: &mut App, tx: &EventSender) {
+    match action {
+        Action::Cancel | Action::Quit | Action::Profiles => app.close_profiles(),
+        Action::MoveDown => app.profiles.next(),
+        Action::MoveUp => app.profiles.previous(),
+        Action::Complete => {
+            let Some(name) = app.selected_profile().map(str::to_string) else {
+                return;
+            };
+            app.close_profiles();
+            if app.profile.as_deref() == Some(name.as_str()) {
+                return;
+            }
+            // `open_profile` shows the loading state once the profile resolves
+            tx.spawn(|tx| async move {
+                // Running a token command can take a moment, so keep it off the event loop
+                let lookup = tokio::task::spawn_blocking(move || Config::for_profile(&name));
+                match lookup.await {
+                    Ok(Ok(config)) => {
+                        tx.send(AppEvent::ProfileLoaded(Box::new(config)));
+                    }
+                    Ok(Err(e)) => {
+                        tx.send(AppEvent::Error(format!("{:#}", e)));
+                    }
+                    Err(e) => {
+                        tx.send(AppEvent::Error(format!("Profile lookup failed: {}", e)));
+                    }
+                }
+            });
+        }
+        _ => {}
+    }
+}
+
+fn save_state(app: &App) {
+    // Losing a preference is not worth interrupting the user over
+    let _ = app.saved_state().save();
+}
+
+/// Reports the client's retries to the UI as `AppEvent::Retrying`.
+pub fn report_retries(client: ApiClient, tx: &EventSender) -> ApiClient {
+    let tx = tx.clone();
+    client.on_retry(move |attempt, max, _| {
+        tx.send(AppEvent::Retrying(attempt, max));
+    })
+}
+
+/// Fetches the chore list in the background and reports the result on `tx`.
+pub fn spawn_load_tasks<A: ChoreApi>(client: &A, tx: &EventSender) {
+    let client = client.clone();
+    tx.spawn(|tx| async move {
+        match client.list_chores().await {
+            Ok(tasks) => {
+                tx.send(AppEvent::TasksLoaded(tasks));
+            }
+            Err(e) if e.is_offline() => {
+                tx.send(AppEvent::Offline(e.to_string()));
+            }
+            Err(e) => {
+                tx.send(AppEvent::ApiError(e));
+            }
+        }
+    });
+}
+
+/// Reloads the list without taking over the screen: failures are reported
+/// in the status line instead of an error dialog.
+pub fn spawn_refresh<A: ChoreApi>(client: &A, tx: &EventSender) {
+    let client = client.clone();
+    tx.spawn(|tx| async move {
+        match client.list_chores().await {
+            Ok(tasks) => {
+                tx.send(AppEvent::TasksRefreshed(tasks));
+            }
+            Err(e) if e.is_offline() => {
+                tx.send(AppEvent::Offline(e.to_string()));
+            }
+            Err(e) => {
+                tx.send(AppEvent::RefreshFailed(e));
+            }
+        }
+    });
+}
+
+/// Sends `AppEvent::Tick` every `period` until the receiver is gone.
+pub fn spawn_ticker(tx: &EventSender, period: Duration) {
+    tx.spawn(|tx| async move {
+        let mut interval = tokio::time::interval(period);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        loop {
+            interval.tick().await;
+            if !tx.send(AppEvent::Tick) {
+                break;
+            }
+        }
+    });
+}
+
+/// Asks for another load attempt after `delay`.
+pub fn schedule_retry(tx: &EventSender, delay: Duration) {
+    tx.spawn(|tx| async move {
+        tokio::time::sleep(delay).await;
+        tx.send(AppEvent::RetryLoad);
+    });
+}
+
+/// Replays the offline outbox against the freshly loaded `tasks`.
+pub fn spawn_replay<A: ChoreApi>(app: &mut App, client: &A, tx: &EventSender) {
+    if app.syncing || app.outbox.is_empty() {
+        return;
+    }
+    app.syncing = true;
+
+    let entries = app.outbox.entries().to_vec();
+    let current: Vec<Chore> = app.tasks.iter().filter(|t| t.id >= 0).cloned().collect();
+    let client = client.clone();
+    tx.spawn(|tx| async move {
+        let outcomes = offline::replay(&client, entries, &current).await;
+        tx.send(AppEvent::OutboxReplayed(outcomes));
+    });
+}
+
 fn handle_error_dialog_input(key: KeyEvent, app: &mut App) {
     if matches!(key.code, KeyCode::Char(_) | KeyCode::Enter | KeyCode::Esc) {
         app.clear_error();
     }
 }
 
-pub async fn poll_events(tx: mpsc::UnboundedSender<AppEvent>) {
-    loop {
-        if event::poll(std::time::Duration::from_millis(100)).unwrap() {
-            if let Ok(Event::Key(key)) = event::read() {
-                let _ = tx.send(AppEvent::Input(key));
+/// Reads terminal input from crossterm's async `EventStream` until the app
+/// shuts down. If the terminal stops delivering input the app is told to
+/// quit, since nothing else could ever end it.
+pub fn spawn_input(tx: &EventSender) {
+    tx.spawn(|tx| async move {
+        let mut events = EventStream::new();
+        loop {
+            let event = match events.next().await {
+                // Terminals that report key releases would otherwise act twice
+                Some(Ok(Event::Key(key))) if key.kind == KeyEventKind::Press => {
+                    AppEvent::Input(key)
+                }
+                Some(Ok(Event::Mouse(mouse))) => AppEvent::Mouse(mouse),
+                Some(Ok(Event::Resize(width, height))) => AppEvent::Resize(width, height),
+                Some(Ok(Event::FocusGained)) => AppEvent::FocusGained,
+                Some(Ok(Event::Paste(text))) => AppEvent::Paste(text),
+                Some(Ok(_)) => continue,
+                Some(Err(e)) => {
+                    tx.send(AppEvent::InputFailed(e.to_string()));
+                    break;
+                }
+                None => {
+                    tx.send(AppEvent::InputFailed("terminal input closed".to_string()));
+                    break;
+                }
+            };
+            if !tx.send(event) {
+                break;
             }
         }
+    });
+}
+
+/// Types pasted text into whichever input is being edited. Line breaks
+/// become spaces, since every input is a single line.
+pub fn handle_paste(text: &str, app: &mut App) {
+    let chars = text
+        .chars()
+        .map(|c| if c == '\n' { ' ' } else { c })
+        .filter(|c| !c.is_control());
+    match app.view {
+        View::TaskList if app.search.editing => chars.for_each(|c| app.push_search_char(c)),
+        View::TaskList if app.filter.editing => chars.for_each(|c| app.push_filter_char(c)),
+        View::AddTask | View::EditTask => chars.for_each(|c| app.form_state.insert_char(c)),
+        View::BulkEdit => {
+            app.bulk_prompt.input.extend(chars);
+            app.bulk_prompt.error = None;
+        }
+        _ => {}
     }
 }
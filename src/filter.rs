@@ -0,0 +1,230 @@
+use crate::api::Chore;
+use crate::dates;
+use crate::sort;
+use chrono::{DateTime, Duration, Local};
+
+/// A parsed filter query such as `label:kitchen due:<7d assignee:me`.
+///
+/// Terms are separated by whitespace and must all match. Prefix a term with
+/// `-` to negate it. Bare words match against the chore name.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Filter {
+    terms: Vec<Term>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Term {
+    negated: bool,
+    condition: Condition,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Condition {
+    Status(String),
+    Label(String),
+    Assignee(String),
+    Active(bool),
+    Due(DueCondition),
+    Priority(Comparison, i32),
+    Text(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum DueCondition {
+    Overdue,
+    Today,
+    Unset,
+    Within(Duration),
+    Beyond(Duration),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Comparison {
+    Less,
+    LessOrEqual,
+    Equal,
+    GreaterOrEqual,
+    Greater,
+}
+
+const STATUSES: [&str; 5] = ["pending", "active", "completed", "archived", "unknown"];
+
+impl Filter {
+    /// Parses a filter query. `me` is the current user's name or id, used to
+    /// resolve `assignee:me`.
+    pub fn parse(input: &str, me: Option<&str>) -> Result<Filter, String> {
+        let terms = input
+            .split_whitespace()
+            .map(|word| parse_term(word, me))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Filter { terms })
+    }
+
+    pub fn matches(&self, chore: &Chore, now: DateTime<Local>) -> bool {
+        self.terms
+            .iter()
+            .all(|term| term.condition.matches(chore, now) != term.negated)
+    }
+}
+
+fn parse_term(word: &str, me: Option<&str>) -> Result<Term, String> {
+    let (negated, word) = match word.strip_prefix('-') {
+        Some(rest) if !rest.is_empty() => (true, rest),
+        _ => (false, word),
+    };
+
+    let condition = match word.split_once(':') {
+        None if word.eq_ignore_ascii_case("overdue") => Condition::Due(DueCondition::Overdue),
+        None => Condition::Text(word.to_lowercase()),
+        Some((key, value)) => {
+            if value.is_empty() {
+                return Err(format!("Missing value for \"{}:\"", key));
+            }
+            parse_condition(&key.to_lowercase(), value, me)?
+        }
+    };
+
+    Ok(Term { negated, condition })
+}
+
+fn parse_condition(key: &str, value: &str, me: Option<&str>) -> Result<Condition, String> {
+    match key {
+        "status" | "is" => {
+            let status = value.to_lowercase();
+            if STATUSES.contains(&status.as_str()) {
+                Ok(Condition::Status(status))
+            } else {
+                Err(format!(
+                    "Unknown status \"{}\" (expected one of {})",
+                    value,
+                    STATUSES.join(", ")
+                ))
+            }
+        }
+        "label" => Ok(Condition::Label(value.to_lowercase())),
+        "assignee" | "assigned" => {
+            if value.eq_ignore_ascii_case("me") {
+                let me =
+                    me.ok_or_else(|| "assignee:me needs DONETICK_USER to be set".to_string())?;
+                Ok(Condition::Assignee(me.to_lowercase()))
+            } else {
+                Ok(Condition::Assignee(value.to_lowercase()))
+            }
+        }
+        "active" => match value.to_lowercase().as_str() {
+            "yes" | "true" => Ok(Condition::Active(true)),
+            "no" | "false" => Ok(Condition::Active(false)),
+            _ => Err(format!("Expected yes or no for active, got \"{}\"", value)),
+        },
+        "due" => parse_due(value).map(Condition::Due),
+        "priority" | "p" => {
+            let (comparison, number) = split_comparison(value);
+            let priority = number
+                .parse::<i32>()
+                .map_err(|_| format!("Invalid priority \"{}\"", value))?;
+            Ok(Condition::Priority(comparison, priority))
+        }
+        _ => Err(format!("Unknown filter \"{}:\"", key)),
+    }
+}
+
+fn parse_due(value: &str) -> Result<DueCondition, String> {
+    match value.to_lowercase().as_str() {
+        "overdue" => return Ok(DueCondition::Overdue),
+        "today" => return Ok(DueCondition::Today),
+        "none" => return Ok(DueCondition::Unset),
+        _ => {}
+    }
+
+    let (comparison, window) = split_comparison(value);
+    let duration = parse_duration(window)
+        .ok_or_else(|| format!("Invalid due window \"{}\" (try due:<7d)", value))?;
+    match comparison {
+        Comparison::Less | Comparison::LessOrEqual => Ok(DueCondition::Within(duration)),
+        Comparison::Greater | Comparison::GreaterOrEqual => Ok(DueCondition::Beyond(duration)),
+        Comparison::Equal => Err(format!("Due windows need < or >, got \"{}\"", value)),
+    }
+}
+
+/// Parses spans like `12h`, `7d` or `2w`.
+fn parse_duration(value: &str) -> Option<Duration> {
+    let unit = value.chars().last()?;
+    let amount = value[..value.len() - unit.len_utf8()].parse::<i64>().ok()?;
+    match unit {
+        'h' => Some(Duration::hours(amount)),
+        'd' => Some(Duration::days(amount)),
+        'w' => Some(Duration::weeks(amount)),
+        _ => None,
+    }
+}
+
+fn split_comparison(value: &str) -> (Comparison, &str) {
+    if let Some(rest) = value.strip_prefix("<=") {
+        (Comparison::LessOrEqual, rest)
+    } else if let Some(rest) = value.strip_prefix(">=") {
+        (Comparison::GreaterOrEqual, rest)
+    } else if let Some(rest) = value.strip_prefix('<') {
+        (Comparison::Less, rest)
+    } else if let Some(rest) = value.strip_prefix('>') {
+        (Comparison::Greater, rest)
+    } else {
+        (Comparison::Equal, value.strip_prefix('=').unwrap_or(value))
+    }
+}
+
+impl Condition {
+    fn matches(&self, chore: &Chore, now: DateTime<Local>) -> bool {
+        match self {
+            Condition::Status(status) => chore.status_name() == status,
+            Condition::Label(label) => chore
+                .labels_v2
+                .iter()
+                .flatten()
+                .any(|l| l.to_lowercase() == *label),
+            Condition::Assignee(who) => {
+                // Chores shared by several people match any of them
+                let id = who.parse::<i64>().ok();
+                let named = |name: &str| name.to_lowercase() == *who;
+                chore.assigned_to.is_some_and(|a| Some(a) == id)
+                    || sort::assignee_name(chore).is_some_and(|name| named(&name))
+                    || chore
+                        .assignees
+                        .iter()
+                        .flatten()
+                        .any(|a| Some(a.id) == id || named(&a.name))
+            }
+            Condition::Active(active) => chore.is_active.unwrap_or(true) == *active,
+            Condition::Due(due) => due.matches(chore, now),
+            Condition::Priority(comparison, wanted) => {
+                chore.priority.is_some_and(|p| comparison.holds(p, *wanted))
+            }
+            Condition::Text(text) => chore.name.to_lowercase().contains(text),
+        }
+    }
+}
+
+impl DueCondition {
+    fn matches(&self, chore: &Chore, now: DateTime<Local>) -> bool {
+        let due = chore.next_due_date.map(|due| due.with_timezone(&Local));
+        match (self, due) {
+            (DueCondition::Overdue, _) => dates::is_overdue(chore, now),
+            (DueCondition::Unset, due) => due.is_none(),
+            (_, None) => false,
+            (DueCondition::Today, Some(due)) => due.date_naive() == now.date_naive(),
+            (DueCondition::Within(window), Some(due)) => due <= now + *window,
+            (DueCondition::Beyond(window), Some(due)) => due > now + *window,
+        }
+    }
+}
+
+impl Comparison {
+    fn holds(self, value: i32, wanted: i32) -> bool {
+        match self {
+            Comparison::Less => value < wanted,
+            Comparison::LessOrEqual => value <= wanted,
+            Comparison::Equal => value == wanted,
+            Comparison::GreaterOrEqual => value >= wanted,
+            Comparison::Greater => value > wanted,
+        }
+    }
+}
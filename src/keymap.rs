@@ -0,0 +1,514 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// Something a key can be bound to. Each action belongs to the task list,
+/// to text inputs (forms, search, filter and prompts), or to both.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    MoveUp,
+    MoveDown,
+    PageUp,
+    PageDown,
+    First,
+    Last,
+    Complete,
+    Add,
+    Edit,
+    Delete,
+    Archive,
+    Undo,
+    Search,
+    Filter,
+    ClearFilter,
+    Sort,
+    ReverseSort,
+    Details,
+    History,
+    Refresh,
+    Profiles,
+    Mark,
+    MarkRange,
+    Relabel,
+    Reassign,
+    Reschedule,
+    Help,
+    Quit,
+    NextField,
+    PreviousField,
+    Submit,
+    /// Esc in both contexts: leaves an input, or clears marks and search
+    Cancel,
+}
+
+/// Which group of bindings a key is looked up in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Context {
+    /// The task list, and the history, profile and help screens
+    List,
+    /// Forms, the search and filter bars, prompts and confirmations
+    Input,
+}
+
+impl Action {
+    /// Every action, in the order the help screen lists them.
+    pub const ALL: [Action; 32] = [
+        Action::MoveUp,
+        Action::MoveDown,
+        Action::PageUp,
+        Action::PageDown,
+        Action::First,
+        Action::Last,
+        Action::Complete,
+        Action::Add,
+        Action::Edit,
+        Action::Delete,
+        Action::Archive,
+        Action::Undo,
+        Action::Search,
+        Action::Filter,
+        Action::ClearFilter,
+        Action::Sort,
+        Action::ReverseSort,
+        Action::Details,
+        Action::History,
+        Action::Refresh,
+        Action::Profiles,
+        Action::Mark,
+        Action::MarkRange,
+        Action::Relabel,
+        Action::Reassign,
+        Action::Reschedule,
+        Action::Help,
+        Action::Quit,
+        Action::NextField,
+        Action::PreviousField,
+        Action::Submit,
+        Action::Cancel,
+    ];
+
+    /// The key used for the action under `[keys]` in the config file.
+    pub fn name(self) -> &'static str {
+        match self {
+            Action::MoveUp => "move_up",
+            Action::MoveDown => "move_down",
+            Action::PageUp => "page_up",
+            Action::PageDown => "page_down",
+            Action::First => "first",
+            Action::Last => "last",
+            Action::Complete => "complete",
+            Action::Add => "add",
+            Action::Edit => "edit",
+            Action::Delete => "delete",
+            Action::Archive => "archive",
+            Action::Undo => "undo",
+            Action::Search => "search",
+            Action::Filter => "filter",
+            Action::ClearFilter => "clear_filter",
+            Action::Sort => "sort",
+            Action::ReverseSort => "reverse_sort",
+            Action::Details => "details",
+            Action::History => "history",
+            Action::Refresh => "refresh",
+            Action::Profiles => "profiles",
+            Action::Mark => "mark",
+            Action::MarkRange => "mark_range",
+            Action::Relabel => "relabel",
+            Action::Reassign => "reassign",
+            Action::Reschedule => "reschedule",
+            Action::Help => "help",
+            Action::Quit => "quit",
+            Action::NextField => "next_field",
+            Action::PreviousField => "previous_field",
+            Action::Submit => "submit",
+            Action::Cancel => "cancel",
+        }
+    }
+
+    /// Short description for the footer and help screen.
+    pub fn description(self) -> &'static str {
+        match self {
+            Action::MoveUp => "up",
+            Action::MoveDown => "down",
+            Action::PageUp => "page up",
+            Action::PageDown => "page down",
+            Action::First => "first task",
+            Action::Last => "last task",
+            Action::Complete => "complete",
+            Action::Add => "add",
+            Action::Edit => "edit",
+            Action::Delete => "delete",
+            Action::Archive => "archive",
+            Action::Undo => "undo",
+            Action::Search => "search",
+            Action::Filter => "filter",
+            Action::ClearFilter => "clear filter",
+            Action::Sort => "sort",
+            Action::ReverseSort => "reverse sort",
+            Action::Details => "details",
+            Action::History => "history",
+            Action::Refresh => "refresh",
+            Action::Profiles => "profile",
+            Action::Mark => "mark",
+            Action::MarkRange => "mark range",
+            Action::Relabel => "labels",
+            Action::Reassign => "assignee",
+            Action::Reschedule => "reschedule",
+            Action::Help => "help",
+            Action::Quit => "quit",
+            Action::NextField => "next field",
+            Action::PreviousField => "previous field",
+            Action::Submit => "submit",
+            Action::Cancel => "cancel",
+        }
+    }
+
+    pub fn in_context(self, context: Context) -> bool {
+        match self {
+            Action::Cancel => true,
+            Action::NextField | Action::PreviousField | Action::Submit => context == Context::Input,
+            _ => context == Context::List,
+        }
+    }
+
+    fn default_keys(self) -> &'static [&'static str] {
+        match self {
+            Action::MoveUp => &["up", "k"],
+            Action::MoveDown => &["down", "j"],
+            Action::PageUp => &["pgup"],
+            Action::PageDown => &["pgdn"],
+            Action::First => &["home", "g"],
+            Action::Last => &["end", "G"],
+            Action::Complete => &["enter"],
+            Action::Add => &["a"],
+            Action::Edit => &["e"],
+            Action::Delete => &["d"],
+            Action::Archive => &["x"],
+            Action::Undo => &["u"],
+            Action::Search => &["/"],
+            Action::Filter => &["f"],
+            Action::ClearFilter => &["F"],
+            Action::Sort => &["s"],
+            Action::ReverseSort => &["S"],
+            Action::Details => &["i"],
+            Action::History => &["h"],
+            Action::Refresh => &["r"],
+            Action::Profiles => &["p"],
+            Action::Mark => &["space"],
+            Action::MarkRange => &["V"],
+            Action::Relabel => &["L"],
+            Action::Reassign => &["A"],
+            Action::Reschedule => &["R"],
+            Action::Help => &["?"],
+            Action::Quit => &["q"],
+            Action::NextField => &["tab"],
+            Action::PreviousField => &["shift+tab"],
+            Action::Submit => &["enter"],
+            Action::Cancel => &["esc"],
+        }
+    }
+}
+
+/// One key with its modifiers. Shift is folded into the character for
+/// letters and symbols, so `G` is just `G`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyPress {
+    pub code: KeyCode,
+    pub modifiers: KeyModifiers,
+}
+
+impl KeyPress {
+    pub fn new(code: KeyCode, modifiers: KeyModifiers) -> Self {
+        let (code, mut modifiers) = match code {
+            KeyCode::BackTab => (KeyCode::Tab, modifiers | KeyModifiers::SHIFT),
+            KeyCode::Char(_) => (code, modifiers - KeyModifiers::SHIFT),
+            _ => (code, modifiers),
+        };
+        modifiers &= KeyModifiers::CONTROL | KeyModifiers::ALT | KeyModifiers::SHIFT;
+        KeyPress { code, modifiers }
+    }
+
+    /// Parses "g", "enter", "ctrl+d" or "shift+tab". Names are case-insensitive.
+    pub fn parse(input: &str) -> Result<Self, String> {
+        // "+" on its own, or as the last part of "ctrl++", is the plus key
+        let (prefix, key) = match input.strip_suffix("++") {
+            Some(prefix) => (Some(prefix), "+"),
+            None => match input.rsplit_once('+') {
+                Some((prefix, key)) if !key.is_empty() => (Some(prefix), key),
+                _ => (None, input),
+            },
+        };
+
+        let mut modifiers = KeyModifiers::NONE;
+        for name in prefix
+            .into_iter()
+            .flat_map(|p| p.split('+'))
+            .filter(|n| !n.is_empty())
+        {
+            modifiers |= match name.to_ascii_lowercase().as_str() {
+                "ctrl" | "control" => KeyModifiers::CONTROL,
+                "alt" | "meta" => KeyModifiers::ALT,
+                "shift" => KeyModifiers::SHIFT,
+                _ => return Err(format!("Unknown modifier \"{}\" in \"{}\"", name, input)),
+            };
+        }
+
+        let code = match named_key(key) {
+            Some(code) => code,
+            None => {
+                let mut chars = key.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) if modifiers.contains(KeyModifiers::SHIFT) => {
+                        KeyCode::Char(c.to_ascii_uppercase())
+                    }
+                    (Some(c), None) => KeyCode::Char(c),
+                    _ => return Err(format!("Unknown key \"{}\"", input)),
+                }
+            }
+        };
+        Ok(KeyPress::new(code, modifiers))
+    }
+
+    /// A key that types text when no binding claims it.
+    fn is_printable(&self) -> bool {
+        matches!(self.code, KeyCode::Char(_))
+            && !self
+                .modifiers
+                .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT)
+    }
+}
+
+impl From<KeyEvent> for KeyPress {
+    fn from(key: KeyEvent) -> Self {
+        KeyPress::new(key.code, key.modifiers)
+    }
+}
+
+fn named_key(name: &str) -> Option<KeyCode> {
+    let code = match name.to_ascii_lowercase().as_str() {
+        "enter" | "return" => KeyCode::Enter,
+        "esc" | "escape" => KeyCode::Esc,
+        "tab" => KeyCode::Tab,
+        "backtab" => KeyCode::BackTab,
+        "space" => KeyCode::Char(' '),
+        "backspace" => KeyCode::Backspace,
+        "del" | "delete" => KeyCode::Delete,
+        "ins" | "insert" => KeyCode::Insert,
+        "up" => KeyCode::Up,
+        "down" => KeyCode::Down,
+        "left" => KeyCode::Left,
+        "right" => KeyCode::Right,
+        "pgup" | "pageup" => KeyCode::PageUp,
+        "pgdn" | "pagedown" => KeyCode::PageDown,
+        "home" => KeyCode::Home,
+        "end" => KeyCode::End,
+        lower => {
+            let n = lower.strip_prefix('f')?.parse().ok()?;
+            KeyCode::F(n)
+        }
+    };
+    Some(code)
+}
+
+impl fmt::Display for KeyPress {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            write!(f, "Ctrl+")?;
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            write!(f, "Alt+")?;
+        }
+        if self.modifiers.contains(KeyModifiers::SHIFT) {
+            write!(f, "Shift+")?;
+        }
+        match self.code {
+            KeyCode::Char(' ') => write!(f, "Space"),
+            KeyCode::Char(c) => write!(f, "{}", c),
+            KeyCode::Enter => write!(f, "Enter"),
+            KeyCode::Esc => write!(f, "Esc"),
+            KeyCode::Tab => write!(f, "Tab"),
+            KeyCode::Backspace => write!(f, "Backspace"),
+            KeyCode::Delete => write!(f, "Del"),
+            KeyCode::Insert => write!(f, "Ins"),
+            KeyCode::Up => write!(f, "↑"),
+            KeyCode::Down => write!(f, "↓"),
+            KeyCode::Left => write!(f, "←"),
+            KeyCode::Right => write!(f, "→"),
+            KeyCode::PageUp => write!(f, "PgUp"),
+            KeyCode::PageDown => write!(f, "PgDn"),
+            KeyCode::Home => write!(f, "Home"),
+            KeyCode::End => write!(f, "End"),
+            KeyCode::F(n) => write!(f, "F{}", n),
+            other => write!(f, "{:?}", other),
+        }
+    }
+}
+
+/// Keys pressed one after another, like `gg` or `ctrl+x ctrl+s`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sequence(pub Vec<KeyPress>);
+
+impl Sequence {
+    /// Parses space-separated keys. A word that is not a key name is read
+    /// one character per key, so "gg" is `g` twice and "G" a single `G`.
+    pub fn parse(input: &str) -> Result<Self, String> {
+        let mut keys = Vec::new();
+        for word in input.split_whitespace() {
+            if word.chars().count() > 1 && !word.contains('+') && named_key(word).is_none() {
+                keys.extend(
+                    word.chars()
+                        .map(|c| KeyPress::new(KeyCode::Char(c), KeyModifiers::NONE)),
+                );
+            } else {
+                keys.push(KeyPress::parse(word)?);
+            }
+        }
+        if keys.is_empty() {
+            return Err("Empty key binding".to_string());
+        }
+        Ok(Sequence(keys))
+    }
+}
+
+impl fmt::Display for Sequence {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // Plain characters run together ("gg"); anything else is spaced out
+        let plain = self
+            .0
+            .iter()
+            .all(|k| k.is_printable() && k.code != KeyCode::Char(' '));
+        for (i, key) in self.0.iter().enumerate() {
+            if i > 0 && !plain {
+                write!(f, " ")?;
+            }
+            write!(f, "{}", key)?;
+        }
+        Ok(())
+    }
+}
+
+/// What the keys pressed so far add up to.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Lookup {
+    Action(Action),
+    /// The start of a longer binding; wait for the next key
+    Pending,
+    None,
+}
+
+/// The key sequences bound to each action.
+#[derive(Debug, Clone)]
+pub struct Keymap {
+    bindings: Vec<(Action, Vec<Sequence>)>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        let bindings = Action::ALL
+            .iter()
+            .map(|&action| {
+                let keys = action.default_keys().iter();
+                let keys = keys.map(|k| Sequence::parse(k).expect("default keys parse"));
+                (action, keys.collect())
+            })
+            .collect();
+        Keymap { bindings }
+    }
+}
+
+impl Keymap {
+    /// The defaults with each action named in `keys` rebound to the listed
+    /// sequences. An empty list unbinds the action.
+    pub fn with_overrides(keys: &BTreeMap<String, Vec<String>>) -> Result<Self, String> {
+        let mut keymap = Keymap::default();
+        for (name, sequences) in keys {
+            let Some(action) = Action::ALL.iter().find(|a| a.name() == name) else {
+                let names: Vec<&str> = Action::ALL.iter().map(|a| a.name()).collect();
+                return Err(format!(
+                    "Unknown action \"{}\"; expected one of: {}",
+                    name,
+                    names.join(", ")
+                ));
+            };
+            let parsed = sequences
+                .iter()
+                .map(|s| Sequence::parse(s).map_err(|e| format!("{} for {}", e, name)))
+                .collect::<Result<Vec<_>, _>>()?;
+            if let Some((_, keys)) = keymap.bindings.iter_mut().find(|(a, _)| a == action) {
+                *keys = parsed;
+            }
+        }
+        keymap.validate()?;
+        Ok(keymap)
+    }
+
+    /// Rejects bindings that could never fire: a sequence that repeats or
+    /// starts with another one in the same context, and plain characters in
+    /// inputs, where they are needed for typing.
+    fn validate(&self) -> Result<(), String> {
+        for context in [Context::List, Context::Input] {
+            let bound: Vec<(Action, &Sequence)> = self.in_context(context).collect();
+            for (i, (action, keys)) in bound.iter().enumerate() {
+                if context == Context::Input && keys.0[0].is_printable() {
+                    return Err(format!(
+                        "\"{}\" for {} would stop it being typed; use a key with ctrl or alt",
+                        keys,
+                        action.name()
+                    ));
+                }
+                for (other, other_keys) in &bound[i + 1..] {
+                    if keys.0.starts_with(&other_keys.0) || other_keys.0.starts_with(&keys.0) {
+                        return Err(format!(
+                            "\"{}\" for {} clashes with \"{}\" for {}",
+                            keys,
+                            action.name(),
+                            other_keys,
+                            other.name()
+                        ));
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn in_context(&self, context: Context) -> impl Iterator<Item = (Action, &Sequence)> {
+        self.bindings
+            .iter()
+            .filter(move |(action, _)| action.in_context(context))
+            .flat_map(|(action, keys)| keys.iter().map(move |k| (*action, k)))
+    }
+
+    pub fn keys(&self, action: Action) -> &[Sequence] {
+        self.bindings
+            .iter()
+            .find(|(a, _)| *a == action)
+            .map(|(_, keys)| keys.as_slice())
+            .unwrap_or_default()
+    }
+
+    /// The first two bindings of `action`, e.g. "↑/k" for the footer.
+    pub fn label(&self, action: Action) -> String {
+        let keys: Vec<String> = self
+            .keys(action)
+            .iter()
+            .take(2)
+            .map(|k| k.to_string())
+            .collect();
+        keys.join("/")
+    }
+
+    /// Matches `pressed` against the bindings of `context`.
+    pub fn lookup(&self, context: Context, pressed: &[KeyPress]) -> Lookup {
+        let mut result = Lookup::None;
+        for (action, keys) in self.in_context(context) {
+            if keys.0 == pressed {
+                return Lookup::Action(action);
+            }
+            if keys.0.starts_with(pressed) {
+                result = Lookup::Pending;
+            }
+        }
+        result
+    }
+}
@@ -1,2 +1,18 @@
 pub mod api;
+pub mod app;
+pub mod bulk;
+pub mod cli;
 pub mod config;
+pub mod dates;
+pub mod diff;
+pub mod event;
+pub mod filter;
+pub mod keymap;
+pub mod offline;
+pub mod recurrence;
+pub mod search;
+pub mod sort;
+pub mod state;
+pub mod theme;
+pub mod ui;
+pub mod undo;
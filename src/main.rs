@@ -1,25 +1,52 @@
-mod api;
-mod app;
-mod config;
-mod event;
-mod ui;
-
 use anyhow::Result;
-use app::App;
-use config::Config;
+use clap::Parser;
 use crossterm::{
+    event::{
+        DisableBracketedPaste, DisableFocusChange, DisableMouseCapture, EnableBracketedPaste,
+        EnableFocusChange, EnableMouseCapture,
+    },
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
-use event::{handle_key_event, poll_events, AppEvent, EventHandler};
+use donetick_tui::app::App;
+use donetick_tui::config::Config;
+use donetick_tui::event::{
+    handle_key_event, handle_mouse_event, handle_paste, report_retries, schedule_retry,
+    spawn_input, spawn_load_tasks, spawn_refresh, spawn_replay, spawn_ticker, AppEvent,
+    EventHandler,
+};
+use donetick_tui::offline::{self, Outcome};
+use donetick_tui::{api, cli, state, ui};
 use ratatui::{backend::CrosstermBackend, Terminal};
 use std::io;
+use std::process::ExitCode;
+use std::time::{Duration, Instant};
+
+/// How long to wait before trying to reach an unreachable server again
+const OFFLINE_RETRY: Duration = Duration::from_secs(30);
+
+/// How often `AppEvent::Tick` arrives
+const TICK: Duration = Duration::from_secs(1);
 
 #[tokio::main]
-async fn main() -> Result<()> {
-    let config = Config::from_env()?;
+async fn main() -> Result<ExitCode> {
+    let cli = cli::Cli::parse();
+    let config = Config::load(cli.profile.as_deref())?;
 
-    let client = api::ApiClient::new(config.donetick_url, config.donetick_token)?;
+    let client = api::ApiClient::new(
+        config.donetick_url.clone(),
+        config.donetick_token.clone(),
+        config.client_settings.clone(),
+    )?;
+
+    // Subcommands drive the API directly and never touch the terminal
+    if let Some(command) = cli.command {
+        let client = client.on_retry(|attempt, max, error| {
+            eprintln!("retrying ({}/{}) after: {}", attempt, max, error);
+        });
+        let user = config.donetick_user.as_deref();
+        return Ok(cli::run(command, &client, user, cli.json).await);
+    }
 
     let mut terminal = setup_terminal()?;
 
@@ -29,17 +56,23 @@ async fn main() -> Result<()> {
         original_hook(panic);
     }));
 
-    let result = run_app(&mut terminal, client).await;
+    let result = run_app(&mut terminal, client, config).await;
 
     restore_terminal()?;
 
-    result
+    result.map(|()| ExitCode::SUCCESS)
 }
 
 fn setup_terminal() -> Result<Terminal<CrosstermBackend<io::Stdout>>> {
     enable_raw_mode()?;
     let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen)?;
+    execute!(
+        stdout,
+        EnterAlternateScreen,
+        EnableBracketedPaste,
+        EnableFocusChange,
+        EnableMouseCapture
+    )?;
     let backend = CrosstermBackend::new(stdout);
     let terminal = Terminal::new(backend)?;
     Ok(terminal)
@@ -47,79 +80,214 @@ fn setup_terminal() -> Result<Terminal<CrosstermBackend<io::Stdout>>> {
 
 fn restore_terminal() -> Result<()> {
     disable_raw_mode()?;
-    execute!(io::stdout(), LeaveAlternateScreen)?;
+    execute!(
+        io::stdout(),
+        DisableMouseCapture,
+        DisableFocusChange,
+        DisableBracketedPaste,
+        LeaveAlternateScreen
+    )?;
     Ok(())
 }
 
 async fn run_app(
     terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
     client: api::ApiClient,
+    config: Config,
 ) -> Result<()> {
     let mut app = App::new();
+    app.restore_state(state::SavedState::load());
+    app.open_profile(config.profile, config.donetick_user);
+    app.refresh_interval = config.refresh_interval;
+    app.keymap = config.keymap;
+    app.theme = config.theme;
     let mut event_handler = EventHandler::new();
-    let tx = event_handler.sender();
-
-    let client_clone = client.clone();
-    let tx_clone = tx.clone();
-    tokio::spawn(async move {
-        match client_clone.list_chores().await {
-            Ok(tasks) => {
-                let _ = tx_clone.send(AppEvent::TasksLoaded(tasks));
-            }
-            Err(e) => {
-                let _ = tx_clone.send(AppEvent::Error(e.to_string()));
-            }
-        }
-    });
+    // Tagged with the open profile, and replaced when it changes
+    let mut tx = event_handler.sender().for_generation(app.generation);
+    let mut client = report_retries(client, &tx);
+
+    spawn_load_tasks(&client, &tx);
 
-    let tx_clone = tx.clone();
-    tokio::spawn(async move {
-        poll_events(tx_clone).await;
-    });
+    spawn_input(&tx);
+    spawn_ticker(&tx, TICK);
 
     while app.running {
-        terminal.draw(|f| ui::draw(f, &app))?;
+        terminal.draw(|f| ui::draw(f, &mut app))?;
+
+        if let Some(event) = event_handler.next_for(app.generation).await {
+            // Any answer from the server ends the current round of retries
+            if matches!(
+                event,
+                AppEvent::TasksLoaded(_)
+                    | AppEvent::TasksRefreshed(_)
+                    | AppEvent::RefreshFailed(_)
+                    | AppEvent::Offline(_)
+                    | AppEvent::HistoryLoaded(..)
+                    | AppEvent::ApiError(_)
+                    | AppEvent::Error(_)
+            ) {
+                app.retrying = None;
+            }
 
-        if let Some(event) = event_handler.next().await {
             match event {
                 AppEvent::Input(key) => {
-                    handle_key_event(key, &mut app, &client, &event_handler.sender()).await;
+                    handle_key_event(key, &mut app, &client, &tx).await;
+                }
+                AppEvent::Mouse(mouse) => {
+                    handle_mouse_event(mouse, &mut app, &client, &tx).await;
+                }
+                AppEvent::Resize(..) => {
+                    terminal.autoresize()?;
+                }
+                AppEvent::FocusGained => {
+                    // Catch up on changes made while away; the next tick reloads
+                    app.refreshed_at = None;
+                }
+                AppEvent::Paste(text) => handle_paste(&text, &mut app),
+                AppEvent::InputFailed(msg) => {
+                    anyhow::bail!("Failed to read terminal input: {}", msg);
+                }
+                AppEvent::Tick => {
+                    let now = Instant::now();
+                    app.expire_highlight(now);
+                    if app.refresh_due(now) {
+                        // Counts as refreshed now so the next tick does not ask again
+                        app.refreshed_at = Some(now);
+                        spawn_refresh(&client, &tx);
+                    }
                 }
                 AppEvent::TasksLoaded(tasks) => {
-                    app.tasks = tasks;
+                    show_tasks(&mut app, tasks, false);
+                    spawn_replay(&mut app, &client, &tx);
+                }
+                AppEvent::TasksRefreshed(tasks) => {
+                    show_tasks(&mut app, tasks, true);
+                    spawn_replay(&mut app, &client, &tx);
+                }
+                AppEvent::RefreshFailed(error) => {
+                    app.notify(format!("Refresh failed: {}", error));
+                }
+                AppEvent::Offline(msg) => {
+                    app.offline = true;
                     app.loading = false;
-                    if !app.tasks.is_empty() && app.selected_task >= app.tasks.len() {
-                        app.selected_task = 0;
+                    app.notify(format!("Offline, showing cached tasks ({})", msg));
+                    if !app.retry_pending {
+                        app.retry_pending = true;
+                        schedule_retry(&tx, OFFLINE_RETRY);
                     }
                 }
-                AppEvent::TaskAdded => {
-                    app.show_task_list();
-                    let client = client.clone();
-                    let tx = event_handler.sender();
-                    tokio::spawn(async move {
-                        match client.list_chores().await {
-                            Ok(tasks) => {
-                                let _ = tx.send(AppEvent::TasksLoaded(tasks));
-                            }
-                            Err(e) => {
-                                let _ = tx.send(AppEvent::Error(e.to_string()));
-                            }
-                        }
-                    });
-                }
-                AppEvent::TaskCompleted => {
-                    let client = client.clone();
-                    let tx = event_handler.sender();
-                    tokio::spawn(async move {
-                        match client.list_chores().await {
-                            Ok(tasks) => {
-                                let _ = tx.send(AppEvent::TasksLoaded(tasks));
+                AppEvent::RetryLoad => {
+                    app.retry_pending = false;
+                    spawn_load_tasks(&client, &tx);
+                }
+                AppEvent::QueueOffline(operation) => {
+                    app.queue_offline(operation);
+                    if !app.retry_pending {
+                        app.retry_pending = true;
+                        schedule_retry(&tx, OFFLINE_RETRY);
+                    }
+                }
+                AppEvent::OutboxReplayed(outcomes) => {
+                    app.syncing = false;
+                    let done: Vec<u64> = outcomes.iter().map(|(id, _)| *id).collect();
+                    let conflicts: Vec<String> = outcomes
+                        .iter()
+                        .filter_map(|(id, outcome)| match outcome {
+                            Outcome::Conflict(reason) => {
+                                let entry = app.outbox.entries().iter().find(|e| e.id == *id)?;
+                                Some(format!("{}: {}", entry.operation.describe(), reason))
                             }
-                            Err(e) => {
-                                let _ = tx.send(AppEvent::Error(e.to_string()));
+                            Outcome::Applied => None,
+                        })
+                        .collect();
+                    let applied = done.len() - conflicts.len();
+
+                    app.outbox.remove(&done);
+                    let _ = app.outbox.save();
+
+                    if !app.outbox.is_empty() && !app.retry_pending {
+                        // Lost the connection part way through
+                        app.retry_pending = true;
+                        schedule_retry(&tx, OFFLINE_RETRY);
+                    }
+                    if applied > 0 {
+                        app.notify(format!("Synced {} queued change(s)", applied));
+                        spawn_load_tasks(&client, &tx);
+                    }
+                    if !conflicts.is_empty() {
+                        app.show_error(format!(
+                            "{} queued change(s) were dropped:\n{}",
+                            conflicts.len(),
+                            conflicts.join("\n")
+                        ));
+                    }
+                }
+                AppEvent::HistoryLoaded(id, entries) => {
+                    // Ignore late responses for a chore that is no longer shown
+                    if app.history.chore_id == id {
+                        app.history.entries = entries;
+                        app.history.loading = false;
+                    }
+                }
+                AppEvent::Retrying(attempt, max) => {
+                    app.retrying = Some(format!("Retrying ({}/{})…", attempt, max));
+                }
+                AppEvent::ProfileLoaded(config) => {
+                    let config = *config;
+                    match api::ApiClient::new(
+                        config.donetick_url,
+                        config.donetick_token,
+                        config.client_settings,
+                    ) {
+                        Ok(new_client) => {
+                            app.open_profile(config.profile, config.donetick_user);
+                            tx = event_handler.sender().for_generation(app.generation);
+                            client = report_retries(new_client, &tx);
+                            app.refresh_interval = config.refresh_interval;
+                            if let Some(name) = &app.profile {
+                                app.notify(format!("Switched to profile {}", name));
                             }
+                            spawn_load_tasks(&client, &tx);
                         }
-                    });
+                        Err(e) => app.show_api_error(&e),
+                    }
+                }
+                AppEvent::TaskAdded(undo) | AppEvent::TaskUpdated(undo) => {
+                    app.show_task_list();
+                    app.push_undo(undo);
+                    spawn_load_tasks(&client, &tx);
+                }
+                AppEvent::TaskCompleted(undo)
+                | AppEvent::TaskDeleted(undo)
+                | AppEvent::TaskArchived(undo) => {
+                    app.push_undo(undo);
+                    spawn_load_tasks(&client, &tx);
+                }
+                AppEvent::Undone(undo) => {
+                    app.notify(format!(
+                        "Undid: {} {}",
+                        undo.verb.to_lowercase(),
+                        undo.subject
+                    ));
+                    spawn_load_tasks(&client, &tx);
+                }
+                AppEvent::UndoFailed(undo, error) => {
+                    app.undo.push(undo);
+                    app.show_api_error(&error);
+                    // Some of a bulk undo may have gone through
+                    spawn_load_tasks(&client, &tx);
+                }
+                AppEvent::BulkProgress(done, _) => {
+                    if let Some(progress) = &mut app.bulk_progress {
+                        progress.done = done;
+                    }
+                }
+                AppEvent::BulkFinished(action, results) => {
+                    app.finish_bulk(&action, results);
+                    spawn_load_tasks(&client, &tx);
+                }
+                AppEvent::ApiError(error) => {
+                    app.show_api_error(&error);
                 }
                 AppEvent::Error(msg) => {
                     app.show_error(msg);
@@ -130,3 +298,20 @@ async fn run_app(
 
     Ok(())
 }
+
+/// Shows a freshly loaded list, highlighting what changed when it came from
+/// the periodic refresh rather than a reload after our own change.
+fn show_tasks(app: &mut App, tasks: Vec<api::Chore>, highlight: bool) {
+    app.refreshed_at = Some(Instant::now());
+    // A stale cache only costs a slower startup next time
+    let _ = offline::save_cached_chores(app.profile.as_deref(), &tasks);
+    if app.offline {
+        app.offline = false;
+        app.status = None;
+    }
+    if highlight {
+        app.refresh_tasks(tasks);
+    } else {
+        app.set_tasks(tasks);
+    }
+}
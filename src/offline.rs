@@ -0,0 +1,196 @@
+use crate::api::{Chore, ChoreApi, CreateChoreRequest};
+use crate::state;
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+const CACHE_FILE: &str = "chores.json";
+const OUTBOX_FILE: &str = "outbox.json";
+
+/// Last chore list fetched from the server, shown on startup before the
+/// network answers.
+pub fn load_cached_chores(profile: Option<&str>) -> Option<Vec<Chore>> {
+    state::load_json(&profile_file(CACHE_FILE, profile))
+}
+
+pub fn save_cached_chores(profile: Option<&str>, chores: &[Chore]) -> Result<()> {
+    state::save_json(&profile_file(CACHE_FILE, profile), &chores)
+}
+
+/// Each profile gets its own cache and outbox, so changes queued against one
+/// server are never replayed against another. "chores.json" becomes
+/// "chores-home.json" for the "home" profile.
+fn profile_file(name: &str, profile: Option<&str>) -> String {
+    let Some(profile) = profile else {
+        return name.to_string();
+    };
+    let profile: String = profile
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    match name.rsplit_once('.') {
+        Some((stem, extension)) => format!("{}-{}.{}", stem, profile, extension),
+        None => format!("{}-{}", name, profile),
+    }
+}
+
+/// Mutations made while the server was unreachable, replayed in order once
+/// it is back. Persisted after every change so nothing is lost on exit.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Outbox {
+    #[serde(skip)]
+    profile: Option<String>,
+    next_id: u64,
+    entries: Vec<QueuedOperation>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QueuedOperation {
+    pub id: u64,
+    pub queued_at: DateTime<Utc>,
+    pub operation: Operation,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Operation {
+    Create {
+        request: CreateChoreRequest,
+    },
+    Complete {
+        chore_id: i64,
+        name: String,
+        /// Due date when the completion was queued, used to detect that
+        /// someone else completed the chore in the meantime
+        due: Option<DateTime<Utc>>,
+    },
+}
+
+#[derive(Debug, Clone)]
+pub enum Outcome {
+    Applied,
+    Conflict(String),
+}
+
+impl Outbox {
+    pub fn load(profile: Option<&str>) -> Self {
+        let mut outbox: Outbox =
+            state::load_json(&profile_file(OUTBOX_FILE, profile)).unwrap_or_default();
+        outbox.profile = profile.map(str::to_string);
+        outbox
+    }
+
+    pub fn save(&self) -> Result<()> {
+        state::save_json(&profile_file(OUTBOX_FILE, self.profile.as_deref()), self)
+    }
+
+    pub fn push(&mut self, operation: Operation) {
+        self.next_id += 1;
+        self.entries.push(QueuedOperation {
+            id: self.next_id,
+            queued_at: Utc::now(),
+            operation,
+        });
+    }
+
+    pub fn remove(&mut self, ids: &[u64]) {
+        self.entries.retain(|entry| !ids.contains(&entry.id));
+    }
+
+    pub fn entries(&self) -> &[QueuedOperation] {
+        &self.entries
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn has_completion(&self, chore_id: i64) -> bool {
+        self.entries.iter().any(|entry| {
+            matches!(entry.operation, Operation::Complete { chore_id: id, .. } if id == chore_id)
+        })
+    }
+
+    /// Stand-in rows for chores created offline. They use negative ids so
+    /// they can never be confused with (or sent to) the server.
+    pub fn placeholders(&self) -> Vec<Chore> {
+        self.entries
+            .iter()
+            .filter_map(|entry| match &entry.operation {
+                Operation::Create { request } => Some(Chore {
+                    id: -(entry.id as i64),
+                    name: request.name.clone(),
+                    next_due_date: request.due_date,
+                    description: request.description.clone(),
+                    priority: request.priority,
+                    labels_v2: request.labels.clone(),
+                    frequency_type: request.frequency_type.clone(),
+                    frequency: request.frequency,
+                    frequency_metadata: request.frequency_metadata.clone(),
+                    is_rolling: request.is_rolling,
+                    ..Chore::default()
+                }),
+                Operation::Complete { .. } => None,
+            })
+            .collect()
+    }
+}
+
+impl Operation {
+    pub fn describe(&self) -> String {
+        match self {
+            Operation::Create { request } => format!("Create \"{}\"", request.name),
+            Operation::Complete { name, .. } => format!("Complete \"{}\"", name),
+        }
+    }
+}
+
+/// Replays queued operations in order against the server. `current` is a
+/// fresh chore list used to spot conflicts. Stops at the first network
+/// failure, leaving the rest queued for the next attempt.
+pub async fn replay<A: ChoreApi>(
+    client: &A,
+    entries: Vec<QueuedOperation>,
+    current: &[Chore],
+) -> Vec<(u64, Outcome)> {
+    let mut outcomes = Vec::new();
+
+    for entry in entries {
+        let result = match &entry.operation {
+            Operation::Create { request } => client.create_chore(request.clone()).await.map(|_| ()),
+            Operation::Complete { chore_id, due, .. } => {
+                match current.iter().find(|c| c.id == *chore_id) {
+                    None => {
+                        let conflict = "the chore no longer exists on the server".to_string();
+                        outcomes.push((entry.id, Outcome::Conflict(conflict)));
+                        continue;
+                    }
+                    Some(chore) if chore.next_due_date != *due => {
+                        let conflict = "it was already completed or rescheduled".to_string();
+                        outcomes.push((entry.id, Outcome::Conflict(conflict)));
+                        continue;
+                    }
+                    Some(_) => client.complete_chore(*chore_id).await,
+                }
+            }
+        };
+
+        match result {
+            Ok(()) => outcomes.push((entry.id, Outcome::Applied)),
+            Err(e) if e.is_offline() => break,
+            Err(e) => outcomes.push((entry.id, Outcome::Conflict(e.to_string()))),
+        }
+    }
+
+    outcomes
+}
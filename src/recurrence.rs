@@ -0,0 +1,234 @@
+use crate::api::Chore;
+use chrono::Weekday;
+use serde_json::{json, Value};
+
+/// How often a chore repeats, mirroring Donetick's `frequencyType`,
+/// `frequency` and `frequencyMetadata` fields.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Recurrence {
+    Once,
+    Daily,
+    Weekly,
+    Monthly,
+    Yearly,
+    Interval { every: i32, unit: IntervalUnit },
+    DaysOfWeek(Vec<Weekday>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum IntervalUnit {
+    Days,
+    Weeks,
+    Months,
+    Years,
+}
+
+impl IntervalUnit {
+    fn name(self) -> &'static str {
+        match self {
+            IntervalUnit::Days => "days",
+            IntervalUnit::Weeks => "weeks",
+            IntervalUnit::Months => "months",
+            IntervalUnit::Years => "years",
+        }
+    }
+
+    fn parse(word: &str) -> Option<IntervalUnit> {
+        match word {
+            "d" | "day" | "days" => Some(IntervalUnit::Days),
+            "w" | "week" | "weeks" => Some(IntervalUnit::Weeks),
+            // Not a bare "m", which means minutes in due dates
+            "mo" | "month" | "months" => Some(IntervalUnit::Months),
+            "y" | "year" | "years" => Some(IntervalUnit::Years),
+            _ => None,
+        }
+    }
+}
+
+impl Recurrence {
+    /// Reads the recurrence of a chore. Returns None for frequency types the
+    /// TUI cannot edit (e.g. adaptive), which are then left untouched.
+    pub fn from_chore(chore: &Chore) -> Option<Recurrence> {
+        let metadata = chore.frequency_metadata.as_ref();
+        match chore.frequency_type.as_deref()? {
+            "once" | "no_repeat" => Some(Recurrence::Once),
+            "daily" => Some(Recurrence::Daily),
+            "weekly" => Some(Recurrence::Weekly),
+            "monthly" => Some(Recurrence::Monthly),
+            "yearly" => Some(Recurrence::Yearly),
+            "interval" => {
+                let unit = metadata
+                    .and_then(|m| m.get("unit"))
+                    .and_then(Value::as_str)
+                    .and_then(IntervalUnit::parse)
+                    .unwrap_or(IntervalUnit::Days);
+                let every = chore.frequency.unwrap_or(1).max(1);
+                Some(Recurrence::Interval { every, unit })
+            }
+            "days_of_the_week" => {
+                let days = metadata
+                    .and_then(|m| m.get("days"))
+                    .and_then(Value::as_array)?
+                    .iter()
+                    .filter_map(Value::as_str)
+                    .filter_map(|d| d.parse::<Weekday>().ok())
+                    .collect();
+                Some(Recurrence::DaysOfWeek(days))
+            }
+            _ => None,
+        }
+    }
+
+    /// Parses the form syntax: `once`, `daily`, `weekly`, `monthly`,
+    /// `yearly`, `every 3 days` or a list of weekdays like `mon wed fri`.
+    pub fn parse(input: &str) -> Result<Recurrence, String> {
+        let input = input.trim().to_lowercase();
+        let words: Vec<&str> = input
+            .split(|c: char| c.is_whitespace() || c == ',')
+            .filter(|w| !w.is_empty())
+            .collect();
+
+        match words.as_slice() {
+            ["once"] | ["never"] => return Ok(Recurrence::Once),
+            ["daily"] | ["every", "day"] => return Ok(Recurrence::Daily),
+            ["weekly"] | ["every", "week"] => return Ok(Recurrence::Weekly),
+            ["monthly"] | ["every", "month"] => return Ok(Recurrence::Monthly),
+            ["yearly"] | ["every", "year"] => return Ok(Recurrence::Yearly),
+            ["every", amount, unit] => {
+                let every = amount
+                    .parse::<i32>()
+                    .ok()
+                    .filter(|n| *n > 0)
+                    .ok_or_else(|| format!("Invalid interval \"{}\"", amount))?;
+                let unit = IntervalUnit::parse(unit)
+                    .ok_or_else(|| format!("Unknown interval unit \"{}\"", unit))?;
+                return Ok(Recurrence::Interval { every, unit });
+            }
+            _ => {}
+        }
+
+        let mut days = Vec::new();
+        for word in &words {
+            let day = word
+                .parse::<Weekday>()
+                .map_err(|_| format!("Unrecognised recurrence \"{}\"", input))?;
+            if !days.contains(&day) {
+                days.push(day);
+            }
+        }
+        if days.is_empty() {
+            return Err("Recurrence is empty".to_string());
+        }
+        days.sort_by_key(|d| d.num_days_from_monday());
+        Ok(Recurrence::DaysOfWeek(days))
+    }
+
+    /// The form text that `parse` reads back into this recurrence.
+    pub fn to_input(&self) -> String {
+        match self {
+            Recurrence::Once => "once".to_string(),
+            Recurrence::Daily => "daily".to_string(),
+            Recurrence::Weekly => "weekly".to_string(),
+            Recurrence::Monthly => "monthly".to_string(),
+            Recurrence::Yearly => "yearly".to_string(),
+            Recurrence::Interval { every, unit } => format!("every {} {}", every, unit.name()),
+            Recurrence::DaysOfWeek(days) => days
+                .iter()
+                .map(|d| short_day(*d).to_lowercase())
+                .collect::<Vec<_>>()
+                .join(" "),
+        }
+    }
+
+    pub fn summary(&self) -> String {
+        match self {
+            Recurrence::Once => "once".to_string(),
+            Recurrence::Daily => "daily".to_string(),
+            Recurrence::Weekly => "weekly".to_string(),
+            Recurrence::Monthly => "monthly".to_string(),
+            Recurrence::Yearly => "yearly".to_string(),
+            Recurrence::Interval { every: 1, unit } => {
+                format!("every {}", unit.name().trim_end_matches('s'))
+            }
+            Recurrence::Interval { every, unit } => format!("every {} {}", every, unit.name()),
+            Recurrence::DaysOfWeek(days) if days.len() == 7 => "every day".to_string(),
+            Recurrence::DaysOfWeek(days) => days
+                .iter()
+                .map(|d| short_day(*d))
+                .collect::<Vec<_>>()
+                .join(", "),
+        }
+    }
+
+    pub fn frequency_type(&self) -> &'static str {
+        match self {
+            Recurrence::Once => "once",
+            Recurrence::Daily => "daily",
+            Recurrence::Weekly => "weekly",
+            Recurrence::Monthly => "monthly",
+            Recurrence::Yearly => "yearly",
+            Recurrence::Interval { .. } => "interval",
+            Recurrence::DaysOfWeek(_) => "days_of_the_week",
+        }
+    }
+
+    pub fn frequency(&self) -> i32 {
+        match self {
+            Recurrence::Interval { every, .. } => *every,
+            _ => 1,
+        }
+    }
+
+    pub fn metadata(&self) -> Option<Value> {
+        match self {
+            Recurrence::Interval { unit, .. } => Some(json!({ "unit": unit.name() })),
+            Recurrence::DaysOfWeek(days) => {
+                let days: Vec<String> = days.iter().map(|d| long_day(*d).to_string()).collect();
+                Some(json!({ "days": days }))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Recurrence summary for display, falling back to the raw frequency type
+/// for kinds the TUI does not model.
+pub fn describe(chore: &Chore) -> String {
+    let summary = match Recurrence::from_chore(chore) {
+        Some(recurrence) => recurrence.summary(),
+        None => match chore.frequency_type.as_deref() {
+            Some(kind) => kind.replace('_', " "),
+            None => return "-".to_string(),
+        },
+    };
+
+    if chore.is_rolling == Some(true) {
+        format!("{} (rolling)", summary)
+    } else {
+        summary
+    }
+}
+
+fn short_day(day: Weekday) -> &'static str {
+    match day {
+        Weekday::Mon => "Mon",
+        Weekday::Tue => "Tue",
+        Weekday::Wed => "Wed",
+        Weekday::Thu => "Thu",
+        Weekday::Fri => "Fri",
+        Weekday::Sat => "Sat",
+        Weekday::Sun => "Sun",
+    }
+}
+
+fn long_day(day: Weekday) -> &'static str {
+    match day {
+        Weekday::Mon => "monday",
+        Weekday::Tue => "tuesday",
+        Weekday::Wed => "wednesday",
+        Weekday::Thu => "thursday",
+        Weekday::Fri => "friday",
+        Weekday::Sat => "saturday",
+        Weekday::Sun => "sunday",
+    }
+}
@@ -0,0 +1,89 @@
+use crate::api::Chore;
+
+/// Result of matching a pattern against a piece of text.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FuzzyMatch {
+    pub score: i64,
+    /// Char indices in the text that matched the pattern
+    pub positions: Vec<usize>,
+}
+
+/// Case-insensitive subsequence match. Consecutive characters and matches at
+/// word starts score higher, so "tot" prefers "Take Out Trash" over "potato".
+pub fn fuzzy_match(pattern: &str, text: &str) -> Option<FuzzyMatch> {
+    let pattern: Vec<char> = pattern.chars().flat_map(char::to_lowercase).collect();
+    if pattern.is_empty() {
+        return Some(FuzzyMatch {
+            score: 0,
+            positions: Vec::new(),
+        });
+    }
+
+    let mut positions = Vec::with_capacity(pattern.len());
+    let mut score = 0;
+    let mut next = 0;
+    let mut previous: Option<char> = None;
+
+    for (index, c) in text.chars().enumerate() {
+        if next == pattern.len() {
+            break;
+        }
+
+        let lower = c.to_lowercase().next().unwrap_or(c);
+        if lower == pattern[next] {
+            score += 1;
+            if positions.last().is_some_and(|&last| last + 1 == index) {
+                score += 5;
+            }
+            if previous.is_none_or(|p| !p.is_alphanumeric()) {
+                score += 3;
+            }
+            positions.push(index);
+            next += 1;
+        }
+        previous = Some(c);
+    }
+
+    if next == pattern.len() {
+        Some(FuzzyMatch { score, positions })
+    } else {
+        None
+    }
+}
+
+/// How well `query` matches the chore, or None when it does not. Every
+/// whitespace-separated term has to match the chore's name, description or
+/// one of its labels, and the score adds up each term's best match.
+pub fn score_chore(query: &str, chore: &Chore) -> Option<i64> {
+    query.split_whitespace().try_fold(0, |total, term| {
+        let name = fuzzy_match(term, &chore.name);
+        let description = chore
+            .description
+            .as_deref()
+            .and_then(|d| fuzzy_match(term, d));
+        let labels = chore
+            .labels_v2
+            .iter()
+            .flatten()
+            .filter_map(|label| fuzzy_match(term, label));
+        let best = name
+            .into_iter()
+            .chain(description)
+            .chain(labels)
+            .map(|m| m.score)
+            .max()?;
+        Some(total + best)
+    })
+}
+
+/// Char indices of `name` to highlight for the given query.
+pub fn name_highlights(query: &str, name: &str) -> Vec<usize> {
+    let mut positions: Vec<usize> = query
+        .split_whitespace()
+        .filter_map(|term| fuzzy_match(term, name))
+        .flat_map(|m| m.positions)
+        .collect();
+    positions.sort_unstable();
+    positions.dedup();
+    positions
+}
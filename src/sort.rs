@@ -0,0 +1,119 @@
+use crate::api::Chore;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SortKey {
+    DueDate,
+    Priority,
+    Name,
+    Status,
+    UpdatedAt,
+    Assignee,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SortOrder {
+    pub key: SortKey,
+    pub descending: bool,
+}
+
+impl SortKey {
+    pub fn next(self) -> SortKey {
+        match self {
+            SortKey::DueDate => SortKey::Priority,
+            SortKey::Priority => SortKey::Name,
+            SortKey::Name => SortKey::Status,
+            SortKey::Status => SortKey::UpdatedAt,
+            SortKey::UpdatedAt => SortKey::Assignee,
+            SortKey::Assignee => SortKey::DueDate,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            SortKey::DueDate => "due date",
+            SortKey::Priority => "priority",
+            SortKey::Name => "name",
+            SortKey::Status => "status",
+            SortKey::UpdatedAt => "last updated",
+            SortKey::Assignee => "assignee",
+        }
+    }
+}
+
+impl Default for SortOrder {
+    fn default() -> Self {
+        Self {
+            key: SortKey::DueDate,
+            descending: false,
+        }
+    }
+}
+
+impl SortOrder {
+    pub fn arrow(&self) -> &'static str {
+        if self.descending {
+            "▼"
+        } else {
+            "▲"
+        }
+    }
+
+    /// Orders two chores by the sort key. Chores missing the value sort last
+    /// in either direction so the interesting rows stay on top.
+    pub fn compare(&self, a: &Chore, b: &Chore) -> Ordering {
+        let ordering = match self.key {
+            SortKey::DueDate => by_value(&a.next_due_date, &b.next_due_date),
+            // Donetick priorities run from 1 (highest) to 4, with 0 meaning unset
+            SortKey::Priority => by_value(
+                &a.priority.filter(|p| *p > 0),
+                &b.priority.filter(|p| *p > 0),
+            ),
+            SortKey::Name => by_value(&Some(a.name.to_lowercase()), &Some(b.name.to_lowercase())),
+            SortKey::Status => by_value(&a.status, &b.status),
+            SortKey::UpdatedAt => by_value(&a.updated_at, &b.updated_at),
+            SortKey::Assignee => by_value(
+                &assignee_name(a).map(|n| n.to_lowercase()),
+                &assignee_name(b).map(|n| n.to_lowercase()),
+            ),
+        };
+
+        match ordering {
+            Missing::Both => Ordering::Equal,
+            Missing::Left => Ordering::Greater,
+            Missing::Right => Ordering::Less,
+            Missing::Neither(ordering) if self.descending => ordering.reverse(),
+            Missing::Neither(ordering) => ordering,
+        }
+    }
+}
+
+enum Missing {
+    Both,
+    Left,
+    Right,
+    Neither(Ordering),
+}
+
+fn by_value<T: Ord>(a: &Option<T>, b: &Option<T>) -> Missing {
+    match (a, b) {
+        (None, None) => Missing::Both,
+        (None, Some(_)) => Missing::Left,
+        (Some(_), None) => Missing::Right,
+        (Some(a), Some(b)) => Missing::Neither(a.cmp(b)),
+    }
+}
+
+/// Display name of the chore's current assignee, falling back to the user id.
+pub fn assignee_name(chore: &Chore) -> Option<String> {
+    let id = chore.assigned_to?;
+    let name = chore
+        .assignees
+        .iter()
+        .flatten()
+        .find(|a| a.id == id && !a.name.is_empty())
+        .map(|a| a.name.clone());
+    Some(name.unwrap_or_else(|| format!("#{}", id)))
+}
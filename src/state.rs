@@ -0,0 +1,52 @@
+use crate::sort::SortOrder;
+use anyhow::{Context, Result};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+
+/// UI preferences remembered between runs.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SavedState {
+    #[serde(default)]
+    pub sort: SortOrder,
+}
+
+impl SavedState {
+    /// Loads the saved state, falling back to defaults when the file is
+    /// missing or unreadable.
+    pub fn load() -> Self {
+        load_json("state.json").unwrap_or_default()
+    }
+
+    pub fn save(&self) -> Result<()> {
+        save_json("state.json", self)
+    }
+}
+
+/// Reads a JSON file from the state directory, returning None when it is
+/// missing or cannot be parsed.
+pub fn load_json<T: DeserializeOwned>(name: &str) -> Option<T> {
+    let text = fs::read_to_string(state_file(name)?).ok()?;
+    serde_json::from_str(&text).ok()
+}
+
+/// Writes a JSON file to the state directory, replacing it atomically so a
+/// crash never leaves a half-written file behind.
+pub fn save_json<T: Serialize>(name: &str, value: &T) -> Result<()> {
+    let path = state_file(name).context("No state directory available")?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+    let text = serde_json::to_string_pretty(value)?;
+    let temp = path.with_extension("json.tmp");
+    fs::write(&temp, text).with_context(|| format!("Failed to write {}", temp.display()))?;
+    fs::rename(&temp, &path).with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok(())
+}
+
+fn state_file(name: &str) -> Option<PathBuf> {
+    let dir = dirs::state_dir().or_else(dirs::data_local_dir)?;
+    Some(dir.join("donetick-tui").join(name))
+}
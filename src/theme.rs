@@ -0,0 +1,320 @@
+use ratatui::style::{Color, Modifier, Style};
+use std::collections::BTreeMap;
+use std::env;
+
+/// Every style the UI draws with, by what it marks rather than its colour.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Theme {
+    /// Column headers
+    pub header: Style,
+    /// The task name in the detail pane and help screen headings
+    pub title: Style,
+    /// The row under the cursor in lists and tables
+    pub selected: Style,
+    /// The selected row when its chore is overdue
+    pub selected_overdue: Style,
+    pub overdue: Style,
+    pub due_soon: Style,
+    /// Rows picked for a bulk action
+    pub marked: Style,
+    /// Rows the last background reload added
+    pub added: Style,
+    /// Rows the last background reload changed
+    pub changed: Style,
+    /// Rows the last background reload dropped
+    pub removed: Style,
+    pub status_active: Style,
+    pub status_completed: Style,
+    pub status_pending: Style,
+    pub status_archived: Style,
+    pub status_other: Style,
+    /// Completions waiting in the offline outbox
+    pub queued: Style,
+    /// Detail labels, the filter bar and status messages
+    pub label: Style,
+    /// Search matches, the search bar and the active form field
+    pub highlight: Style,
+    /// Keys on the help screen
+    pub key: Style,
+    /// Progress, retries, hints and confirmation titles
+    pub warning: Style,
+    pub error: Style,
+    pub success: Style,
+    /// Hints that explain what to do next
+    pub muted: Style,
+    /// Dialogs drawn over the list
+    pub popup: Style,
+    /// The key hints bar along the bottom
+    pub footer: Style,
+    /// Keys in the footer
+    pub footer_key: Style,
+}
+
+/// A role's colours and modifiers as written in the config file. Unset
+/// colours keep the base theme's.
+#[derive(Debug, Clone, Default)]
+pub struct StyleSpec {
+    pub fg: Option<String>,
+    pub bg: Option<String>,
+    pub modifiers: Vec<String>,
+}
+
+/// The theme used when the config does not pick one: `monochrome` when
+/// `NO_COLOR` is set, `dark` otherwise.
+pub fn default_name() -> &'static str {
+    match env::var_os("NO_COLOR") {
+        Some(value) if !value.is_empty() => "monochrome",
+        _ => "dark",
+    }
+}
+
+fn fg(color: Color) -> Style {
+    Style::default().fg(color)
+}
+
+fn with(modifier: Modifier) -> Style {
+    Style::default().add_modifier(modifier)
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Theme::dark()
+    }
+}
+
+impl Theme {
+    /// Names of the built-in themes.
+    pub const NAMES: [&'static str; 4] = ["dark", "light", "high-contrast", "monochrome"];
+
+    pub fn built_in(name: &str) -> Option<Theme> {
+        match name {
+            "dark" => Some(Theme::dark()),
+            "light" => Some(Theme::light()),
+            "high-contrast" => Some(Theme::high_contrast()),
+            "monochrome" => Some(Theme::monochrome()),
+            _ => None,
+        }
+    }
+
+    /// Light text on a dark terminal background.
+    pub fn dark() -> Self {
+        Theme {
+            header: with(Modifier::BOLD),
+            title: with(Modifier::BOLD),
+            selected: Style::default().bg(Color::DarkGray).fg(Color::White),
+            selected_overdue: Style::default().bg(Color::DarkGray).fg(Color::LightRed),
+            overdue: fg(Color::Red),
+            due_soon: fg(Color::Yellow),
+            marked: fg(Color::Cyan),
+            added: fg(Color::Green),
+            changed: fg(Color::Yellow),
+            removed: fg(Color::DarkGray).add_modifier(Modifier::CROSSED_OUT),
+            status_active: fg(Color::Green),
+            status_completed: fg(Color::Blue),
+            status_pending: fg(Color::Yellow),
+            status_archived: fg(Color::DarkGray),
+            status_other: fg(Color::White),
+            queued: fg(Color::Magenta),
+            label: fg(Color::Cyan),
+            highlight: fg(Color::Yellow).add_modifier(Modifier::BOLD),
+            key: fg(Color::Yellow),
+            warning: fg(Color::Yellow),
+            error: fg(Color::Red),
+            success: fg(Color::Green),
+            muted: fg(Color::Gray),
+            popup: Style::default().bg(Color::Black),
+            footer: Style::default().bg(Color::DarkGray),
+            footer_key: with(Modifier::BOLD),
+        }
+    }
+
+    /// Dark text on a light terminal background, avoiding yellow and cyan.
+    pub fn light() -> Self {
+        Theme {
+            header: with(Modifier::BOLD),
+            title: with(Modifier::BOLD),
+            selected: Style::default().bg(Color::Gray).fg(Color::Black),
+            selected_overdue: Style::default().bg(Color::Gray).fg(Color::Red),
+            overdue: fg(Color::Red),
+            due_soon: fg(Color::Magenta),
+            marked: fg(Color::Blue),
+            added: fg(Color::Green),
+            changed: fg(Color::Magenta),
+            removed: fg(Color::DarkGray).add_modifier(Modifier::CROSSED_OUT),
+            status_active: fg(Color::Green),
+            status_completed: fg(Color::Blue),
+            status_pending: fg(Color::Magenta),
+            status_archived: fg(Color::DarkGray),
+            status_other: fg(Color::Black),
+            queued: fg(Color::Blue).add_modifier(Modifier::ITALIC),
+            label: fg(Color::Blue),
+            highlight: fg(Color::Magenta).add_modifier(Modifier::BOLD),
+            key: fg(Color::Blue),
+            warning: fg(Color::Magenta),
+            error: fg(Color::Red),
+            success: fg(Color::Green),
+            muted: fg(Color::DarkGray),
+            popup: Style::default().bg(Color::White).fg(Color::Black),
+            footer: Style::default().bg(Color::Gray).fg(Color::Black),
+            footer_key: with(Modifier::BOLD),
+        }
+    }
+
+    /// Bright colours, bold text and reversed selection.
+    pub fn high_contrast() -> Self {
+        Theme {
+            header: with(Modifier::BOLD | Modifier::UNDERLINED),
+            title: with(Modifier::BOLD),
+            selected: with(Modifier::REVERSED | Modifier::BOLD),
+            selected_overdue: fg(Color::LightRed).add_modifier(Modifier::REVERSED | Modifier::BOLD),
+            overdue: fg(Color::LightRed).add_modifier(Modifier::BOLD),
+            due_soon: fg(Color::LightYellow),
+            marked: fg(Color::LightCyan).add_modifier(Modifier::BOLD),
+            added: fg(Color::LightGreen),
+            changed: fg(Color::LightYellow),
+            removed: fg(Color::Gray).add_modifier(Modifier::CROSSED_OUT),
+            status_active: fg(Color::LightGreen),
+            status_completed: fg(Color::LightBlue),
+            status_pending: fg(Color::LightYellow),
+            status_archived: fg(Color::Gray),
+            status_other: fg(Color::White),
+            queued: fg(Color::LightMagenta),
+            label: fg(Color::LightCyan),
+            highlight: fg(Color::LightYellow).add_modifier(Modifier::BOLD | Modifier::UNDERLINED),
+            key: fg(Color::LightYellow).add_modifier(Modifier::BOLD),
+            warning: fg(Color::LightYellow),
+            error: fg(Color::LightRed).add_modifier(Modifier::BOLD),
+            success: fg(Color::LightGreen),
+            muted: fg(Color::White),
+            popup: Style::default().bg(Color::Black).fg(Color::White),
+            footer: with(Modifier::REVERSED),
+            footer_key: with(Modifier::BOLD),
+        }
+    }
+
+    /// No colours at all, only bold, reversed and similar modifiers.
+    pub fn monochrome() -> Self {
+        Theme {
+            header: with(Modifier::BOLD),
+            title: with(Modifier::BOLD),
+            selected: with(Modifier::REVERSED),
+            selected_overdue: with(Modifier::REVERSED | Modifier::BOLD),
+            overdue: with(Modifier::BOLD),
+            due_soon: with(Modifier::UNDERLINED),
+            marked: with(Modifier::UNDERLINED),
+            added: with(Modifier::ITALIC),
+            changed: with(Modifier::ITALIC),
+            removed: with(Modifier::DIM | Modifier::CROSSED_OUT),
+            status_active: Style::default(),
+            status_completed: Style::default(),
+            status_pending: Style::default(),
+            status_archived: with(Modifier::DIM),
+            status_other: Style::default(),
+            queued: with(Modifier::ITALIC),
+            label: with(Modifier::BOLD),
+            highlight: with(Modifier::BOLD | Modifier::UNDERLINED),
+            key: with(Modifier::BOLD),
+            warning: Style::default(),
+            error: with(Modifier::BOLD),
+            success: Style::default(),
+            muted: with(Modifier::DIM),
+            popup: Style::default(),
+            footer: with(Modifier::REVERSED),
+            footer_key: with(Modifier::BOLD),
+        }
+    }
+
+    /// Each role's name in the config file, with its style.
+    fn roles_mut(&mut self) -> [(&'static str, &mut Style); 26] {
+        [
+            ("header", &mut self.header),
+            ("title", &mut self.title),
+            ("selected", &mut self.selected),
+            ("selected_overdue", &mut self.selected_overdue),
+            ("overdue", &mut self.overdue),
+            ("due_soon", &mut self.due_soon),
+            ("marked", &mut self.marked),
+            ("added", &mut self.added),
+            ("changed", &mut self.changed),
+            ("removed", &mut self.removed),
+            ("status_active", &mut self.status_active),
+            ("status_completed", &mut self.status_completed),
+            ("status_pending", &mut self.status_pending),
+            ("status_archived", &mut self.status_archived),
+            ("status_other", &mut self.status_other),
+            ("queued", &mut self.queued),
+            ("label", &mut self.label),
+            ("highlight", &mut self.highlight),
+            ("key", &mut self.key),
+            ("warning", &mut self.warning),
+            ("error", &mut self.error),
+            ("success", &mut self.success),
+            ("muted", &mut self.muted),
+            ("popup", &mut self.popup),
+            ("footer", &mut self.footer),
+            ("footer_key", &mut self.footer_key),
+        ]
+    }
+
+    /// This theme with each role named in `styles` patched: colours given
+    /// there replace the role's, and modifiers are added to it.
+    pub fn with_overrides(mut self, styles: &BTreeMap<String, StyleSpec>) -> Result<Self, String> {
+        for (name, spec) in styles {
+            let patch = parse_style(spec).map_err(|e| format!("{} for {}", e, name))?;
+            let mut roles = self.roles_mut();
+            let Some((_, style)) = roles.iter_mut().find(|(role, _)| role == name) else {
+                let names: Vec<&str> = roles.iter().map(|(role, _)| *role).collect();
+                return Err(format!(
+                    "Unknown style \"{}\"; expected one of: {}",
+                    name,
+                    names.join(", ")
+                ));
+            };
+            **style = style.patch(patch);
+        }
+        Ok(self)
+    }
+}
+
+fn parse_style(spec: &StyleSpec) -> Result<Style, String> {
+    let mut style = Style::default();
+    if let Some(color) = &spec.fg {
+        style = style.fg(parse_color(color)?);
+    }
+    if let Some(color) = &spec.bg {
+        style = style.bg(parse_color(color)?);
+    }
+    for name in &spec.modifiers {
+        style = style.add_modifier(parse_modifier(name)?);
+    }
+    Ok(style)
+}
+
+/// A colour name such as `red` or `dark-gray`, a palette index, or `#rrggbb`.
+fn parse_color(color: &str) -> Result<Color, String> {
+    color.parse().map_err(|_| {
+        format!(
+            "\"{}\" is not a colour name, palette index or #rrggbb",
+            color
+        )
+    })
+}
+
+fn parse_modifier(name: &str) -> Result<Modifier, String> {
+    let modifier = match name.to_lowercase().replace(['-', ' '], "_").as_str() {
+        "bold" => Modifier::BOLD,
+        "dim" => Modifier::DIM,
+        "italic" => Modifier::ITALIC,
+        "underlined" | "underline" => Modifier::UNDERLINED,
+        "reversed" | "reverse" => Modifier::REVERSED,
+        "crossed_out" | "strikethrough" => Modifier::CROSSED_OUT,
+        "slow_blink" | "blink" => Modifier::SLOW_BLINK,
+        _ => {
+            return Err(format!(
+                "Unknown modifier \"{}\"; expected bold, dim, italic, underlined, reversed, crossed_out or blink",
+                name
+            ))
+        }
+    };
+    Ok(modifier)
+}
@@ -1,145 +1,801 @@
-use crate::app::{App, FormField, View};
+use crate::api::Chore;
+use crate::app::{App, BulkField, FormField, Regions, View};
+use crate::dates;
+use crate::diff::Change;
+use crate::keymap::{Action, Context};
+use crate::recurrence;
+use crate::search;
+use crate::sort::{self, SortKey};
+use crate::theme::Theme;
+use chrono::{DateTime, Local, Utc};
 use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout, Rect},
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Cell, Paragraph, Row, Table},
+    widgets::{
+        Block, Borders, Cell, Clear, Paragraph, Row, Scrollbar, ScrollbarOrientation,
+        ScrollbarState, Table, TableState, Wrap,
+    },
     Frame,
 };
+use serde_json::Value;
 
-pub fn draw(f: &mut Frame, app: &App) {
+pub fn draw(f: &mut Frame, app: &mut App) {
+    draw_at(f, app, Local::now());
+}
+
+/// Draws the app with relative due dates measured from `now`, so snapshot
+/// tests render the same output whenever they run.
+pub fn draw_at(f: &mut Frame, app: &mut App, now: DateTime<Local>) {
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([Constraint::Min(3), Constraint::Length(1)])
         .split(f.area());
 
+    app.regions = Regions::default();
     match app.view {
         View::TaskList => {
-            draw_task_list(f, chunks[0], app);
-            draw_help_footer(f, chunks[1], &app.view);
+            draw_task_list(f, chunks[0], app, now);
+            draw_help_footer(f, chunks[1], app);
         }
-        View::AddTask => {
-            draw_add_form(f, chunks[0], app);
-            draw_help_footer(f, chunks[1], &app.view);
+        View::AddTask | View::EditTask => {
+            draw_add_form(f, chunks[0], app, now);
+            draw_help_footer(f, chunks[1], app);
         }
         View::ErrorDialog => {
-            draw_task_list(f, chunks[0], app);
+            draw_task_list(f, chunks[0], app, now);
             draw_error_dialog(f, f.area(), app);
-            draw_help_footer(f, chunks[1], &app.view);
+            draw_help_footer(f, chunks[1], app);
+        }
+        View::History => {
+            draw_history(f, chunks[0], app);
+            draw_help_footer(f, chunks[1], app);
+        }
+        View::Confirm => {
+            draw_task_list(f, chunks[0], app, now);
+            draw_confirm_dialog(f, f.area(), app);
+            draw_help_footer(f, chunks[1], app);
+        }
+        View::Profiles => {
+            draw_task_list(f, chunks[0], app, now);
+            draw_profile_picker(f, f.area(), app);
+            draw_help_footer(f, chunks[1], app);
+        }
+        View::BulkEdit => {
+            draw_task_list(f, chunks[0], app, now);
+            draw_bulk_prompt(f, f.area(), app, now);
+            draw_help_footer(f, chunks[1], app);
+        }
+        View::BulkSummary => {
+            draw_task_list(f, chunks[0], app, now);
+            draw_bulk_summary(f, f.area(), app);
+            draw_help_footer(f, chunks[1], app);
+        }
+        View::Help => {
+            draw_help(f, chunks[0], app);
+            draw_help_footer(f, chunks[1], app);
         }
     }
 }
 
-fn draw_task_list(f: &mut Frame, area: Rect, app: &App) {
+fn status_label(task: &Chore, theme: &Theme) -> (&'static str, Style) {
+    let name = task.status_name();
+    let style = match name {
+        "archived" => theme.status_archived,
+        "active" => theme.status_active,
+        "completed" => theme.status_completed,
+        "pending" => theme.status_pending,
+        _ => theme.status_other,
+    };
+    (name, style)
+}
+
+fn draw_task_list(f: &mut Frame, area: Rect, app: &mut App, now: DateTime<Local>) {
+    let area = if app.show_details && !app.loading {
+        let columns = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(55), Constraint::Percentage(45)])
+            .split(area);
+        draw_task_detail(f, columns[1], app, now);
+        columns[0]
+    } else {
+        area
+    };
+
     if app.loading {
         let loading = Paragraph::new("Loading tasks...")
-            .block(Block::default().borders(Borders::ALL).title("Donetick Tasks"))
+            .block(status_block(app, "Donetick Tasks".to_string()))
             .alignment(Alignment::Center);
         f.render_widget(loading, area);
         return;
     }
 
-    if app.tasks.is_empty() {
-        let empty = Paragraph::new("No tasks found. Press 'a' to add a new task.")
-            .block(Block::default().borders(Borders::ALL).title("Donetick Tasks"))
+    let show_filter = app.filter.editing || !app.filter.input.is_empty();
+    let show_search = app.search.editing || !app.search.query.is_empty();
+    let bars = [show_filter, show_search]
+        .iter()
+        .filter(|shown| **shown)
+        .count();
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(bars as u16), Constraint::Min(0)])
+        .split(area);
+    let bar_rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(vec![Constraint::Length(1); bars])
+        .split(rows[0]);
+    let mut bar_rows = bar_rows.iter();
+    if show_filter {
+        if let Some(bar) = bar_rows.next() {
+            draw_filter_bar(f, *bar, app);
+        }
+    }
+    if show_search {
+        if let Some(bar) = bar_rows.next() {
+            draw_search_bar(f, *bar, app);
+        }
+    }
+    let area = rows[1];
+
+    if app.visible.is_empty() {
+        let message = if app.tasks.is_empty() {
+            "No tasks found. Press 'a' to add a new task.".to_string()
+        } else {
+            "No tasks match the current search or filter.".to_string()
+        };
+        let empty = Paragraph::new(message)
+            .block(status_block(app, "Donetick Tasks".to_string()))
             .alignment(Alignment::Center);
         f.render_widget(empty, area);
         return;
     }
 
+    let header_cell = |title: &str, key: SortKey| {
+        let text = if app.sort.key == key {
+            format!("{} {}", title, app.sort.arrow())
+        } else {
+            title.to_string()
+        };
+        Cell::from(text).style(app.theme.header)
+    };
     let header = Row::new(vec![
-        Cell::from("Name").style(Style::default().add_modifier(Modifier::BOLD)),
-        Cell::from("Due Date").style(Style::default().add_modifier(Modifier::BOLD)),
-        Cell::from("Status").style(Style::default().add_modifier(Modifier::BOLD)),
-        Cell::from("Priority").style(Style::default().add_modifier(Modifier::BOLD)),
+        header_cell("Name", SortKey::Name),
+        header_cell("Due Date", SortKey::DueDate),
+        Cell::from("Repeats").style(app.theme.header),
+        header_cell("Status", SortKey::Status),
+        header_cell("Priority", SortKey::Priority),
+        header_cell("Assignee", SortKey::Assignee),
     ]);
 
-    let rows: Vec<Row> = app.tasks.iter().enumerate().map(|(i, task)| {
-        let style = if i == app.selected_task {
-            Style::default().bg(Color::DarkGray).fg(Color::White)
-        } else {
-            Style::default()
+    let theme = &app.theme;
+    let rows: Vec<Row> = app
+        .visible_tasks()
+        .enumerate()
+        .map(|(i, task)| {
+            let overdue = dates::is_overdue(task, now);
+            let marked = app.is_marked(i);
+            let style = match (i == app.selected_task, overdue) {
+                (true, true) => theme.selected_overdue,
+                (true, false) => theme.selected,
+                (false, true) => theme.overdue,
+                (false, false) if marked => theme.marked,
+                (false, false) => match app.change(task.id) {
+                    Some(Change::Added) => theme.added,
+                    Some(Change::Changed) => theme.changed,
+                    _ => Style::default(),
+                },
+            };
+            let style = if marked {
+                style.add_modifier(Modifier::BOLD)
+            } else {
+                style
+            };
+
+            let (status_text, status_style) = if task.id < 0 || app.outbox.has_completion(task.id) {
+                ("queued", theme.queued)
+            } else {
+                status_label(task, theme)
+            };
+
+            // Highlight chores coming due within the next day
+            let due_style = match task.next_due_date {
+                Some(due) if !overdue && due < now + chrono::Duration::days(1) => theme.due_soon,
+                _ => Style::default(),
+            };
+            let due_text = task
+                .next_due_date
+                .map(|due| dates::relative_due(due, now))
+                .unwrap_or_else(|| "-".to_string());
+
+            let mut name = highlight_matches(&task.name, &app.search.query, theme.highlight);
+            if marked {
+                name.spans.insert(0, Span::raw("● "));
+            }
+
+            Row::new(vec![
+                Cell::from(name),
+                Cell::from(due_text).style(due_style),
+                Cell::from(recurrence::describe(task)),
+                Cell::from(status_text).style(status_style),
+                Cell::from(
+                    task.priority
+                        .map(|p| p.to_string())
+                        .unwrap_or_else(|| "-".to_string()),
+                ),
+                Cell::from(sort::assignee_name(task).unwrap_or_else(|| "-".to_string())),
+            ])
+            .style(style)
+        })
+        .collect();
+
+    // Chores the last reload dropped stay below the list until the highlight fades
+    let removed: Vec<Row> = app
+        .highlight
+        .removed
+        .iter()
+        .map(|task| {
+            Row::new(vec![Cell::from(task.name.clone()), Cell::from("removed")])
+                .style(theme.removed)
+        })
+        .collect();
+
+    let widths = [
+        Constraint::Percentage(30),
+        Constraint::Percentage(15),
+        Constraint::Percentage(16),
+        Constraint::Percentage(12),
+        Constraint::Percentage(9),
+        Constraint::Percentage(18),
+    ];
+
+    let mut title = format!(
+        "Donetick Tasks (sorted by {} {})",
+        app.sort.key.label(),
+        app.sort.arrow()
+    );
+    if let Some(profile) = &app.profile {
+        title.push_str(&format!(" [{}]", profile));
+    }
+    if app.offline {
+        title.push_str(" [offline]");
+    }
+    if app.syncing {
+        title.push_str(" [syncing]");
+    } else if !app.outbox.is_empty() {
+        title.push_str(&format!(" [{} queued]", app.outbox.len()));
+    }
+    if app.has_marks() {
+        title.push_str(&format!(" [{} marked]", app.bulk_targets().len()));
+    }
+    if app.visual_anchor.is_some() {
+        title.push_str(" [VISUAL]");
+    }
+
+    let total = app.visible.len();
+    let position = Line::from(format!(" {} of {} ", app.selected_task + 1, total)).right_aligned();
+    let block = status_block(app, title).title_bottom(position);
+
+    // Removed rows get their own strip along the bottom, outside the
+    // scrolling list, and never take more than half the space
+    let inner = block.inner(area);
+    let removed_height = (removed.len() as u16).min(inner.height.saturating_sub(1) / 2);
+    let parts = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(0), Constraint::Length(removed_height)])
+        .split(inner);
+    let (list_area, removed_area) = (parts[0], parts[1]);
+    f.render_widget(block, area);
+    f.render_widget(Table::new(removed, widths), removed_area);
+
+    let table = Table::new(rows, widths).header(header);
+
+    // The list's rows, below the header
+    app.page_size = list_area.height.saturating_sub(1).max(1) as usize;
+    let mut state = TableState::default()
+        .with_offset(app.scroll_offset)
+        .with_selected(Some(app.selected_task));
+    f.render_stateful_widget(table, list_area, &mut state);
+    app.scroll_offset = state.offset();
+    app.regions.task_rows = Rect {
+        x: area.x + 1,
+        y: area.y + 2,
+        width: area.width.saturating_sub(2),
+        height: app.page_size as u16,
+    }
+    .intersection(area);
+
+    if total > app.page_size {
+        // One position per possible offset, so the thumb reaches the bottom
+        // exactly when the last task comes into view
+        let mut scrollbar = ScrollbarState::new(total - app.page_size + 1)
+            .viewport_content_length(app.page_size)
+            .position(app.scroll_offset);
+        // Along the right border, beside the task rows only
+        let scrollbar_area = Rect {
+            y: area.y + 2,
+            height: app.page_size as u16,
+            ..area
         };
+        f.render_stateful_widget(
+            Scrollbar::new(ScrollbarOrientation::VerticalRight)
+                .begin_symbol(None)
+                .end_symbol(None),
+            scrollbar_area,
+            &mut scrollbar,
+        );
+    }
+}
+
+/// Bordered block with bulk or retry progress, or the status message,
+/// along the bottom edge.
+fn status_block(app: &App, title: String) -> Block<'static> {
+    let block = Block::default().borders(Borders::ALL).title(title);
+    let message = match (&app.bulk_progress, &app.retrying, &app.status) {
+        (Some(progress), _, _) => Span::styled(
+            format!(
+                " {} {}/{} {} ",
+                progress.verb,
+                progress.done,
+                progress.total,
+                progress_bar(progress.done, progress.total)
+            ),
+            app.theme.warning,
+        ),
+        (None, Some(retrying), _) => Span::styled(format!(" {} ", retrying), app.theme.warning),
+        (None, None, Some(status)) => Span::styled(format!(" {} ", status), app.theme.label),
+        (None, None, None) => return block,
+    };
+    block.title_bottom(Line::from(message))
+}
+
+/// A ten-segment bar, e.g. "▰▰▰▱▱▱▱▱▱▱" for 3 of 10.
+fn progress_bar(done: usize, total: usize) -> String {
+    const SEGMENTS: usize = 10;
+    let filled = (done * SEGMENTS).checked_div(total).unwrap_or(SEGMENTS);
+    format!("{}{}", "▰".repeat(filled), "▱".repeat(SEGMENTS - filled))
+}
+
+fn draw_filter_bar(f: &mut Frame, area: Rect, app: &App) {
+    let mut spans = vec![
+        Span::styled("Filter: ", app.theme.label.add_modifier(Modifier::BOLD)),
+        Span::raw(app.filter.input.clone()),
+    ];
+    if app.filter.editing {
+        spans.push(Span::styled("█", app.theme.label));
+    }
+    match &app.filter.error {
+        Some(error) => spans.push(Span::styled(format!("  {}", error), app.theme.error)),
+        None => spans.push(Span::styled(
+            format!("  {} of {} tasks", app.visible.len(), app.tasks.len()),
+            app.theme.muted,
+        )),
+    }
+
+    f.render_widget(Paragraph::new(Line::from(spans)), area);
+}
+
+fn draw_search_bar(f: &mut Frame, area: Rect, app: &App) {
+    let mut spans = vec![
+        Span::styled("/", app.theme.highlight),
+        Span::raw(app.search.query.clone()),
+    ];
+    if app.search.editing {
+        spans.push(Span::styled("█", app.theme.highlight));
+    }
+    spans.push(Span::styled(
+        format!("  {} of {} tasks", app.visible.len(), app.tasks.len()),
+        app.theme.muted,
+    ));
+
+    f.render_widget(Paragraph::new(Line::from(spans)), area);
+}
+
+/// Splits `text` into spans, emphasising the characters matched by `query`.
+fn highlight_matches(text: &str, query: &str, highlight: Style) -> Line<'static> {
+    let positions = search::name_highlights(query, text);
+    if positions.is_empty() {
+        return Line::from(text.to_string());
+    }
+
+    let mut spans = Vec::new();
+    let mut current = String::new();
+    let mut current_matched = false;
+    for (index, c) in text.chars().enumerate() {
+        let matched = positions.binary_search(&index).is_ok();
+        if matched != current_matched && !current.is_empty() {
+            let style = if current_matched {
+                highlight
+            } else {
+                Style::default()
+            };
+            spans.push(Span::styled(std::mem::take(&mut current), style));
+        }
+        current_matched = matched;
+        current.push(c);
+    }
+    let style = if current_matched {
+        highlight
+    } else {
+        Style::default()
+    };
+    spans.push(Span::styled(current, style));
+
+    Line::from(spans)
+}
+
+fn draw_task_detail(f: &mut Frame, area: Rect, app: &App, now: DateTime<Local>) {
+    let block = Block::default().borders(Borders::ALL).title("Details");
+
+    let Some(task) = app.select_task() else {
+        let empty = Paragraph::new("No task selected")
+            .block(block)
+            .alignment(Alignment::Center);
+        f.render_widget(empty, area);
+        return;
+    };
+
+    let theme = &app.theme;
+    let detail_line = |label: &str, value: String| detail_line(label, value, theme);
+    let (status_text, status_style) = status_label(task, theme);
+    let mut lines = vec![
+        Line::from(Span::styled(task.name.clone(), theme.title)),
+        Line::from(""),
+        detail_line("ID", task.id.to_string()),
+        Line::from(vec![
+            Span::styled("Status: ", theme.label),
+            Span::styled(status_text, status_style),
+        ]),
+        detail_line("Active", format_bool(task.is_active)),
+        detail_line("Priority", format_opt(task.priority)),
+        detail_line(
+            "Next due",
+            task.next_due_date
+                .map(|due| {
+                    format!(
+                        "{} ({})",
+                        dates::format_local(due),
+                        dates::relative_due(due, now)
+                    )
+                })
+                .unwrap_or_else(|| "-".to_string()),
+        ),
+        detail_line("Repeats", recurrence::describe(task)),
+        detail_line("Rolling", format_bool(task.is_rolling)),
+    ];
+    lines.extend(json_lines(
+        "Frequency metadata",
+        task.frequency_metadata.as_ref(),
+        theme,
+    ));
+
+    lines.push(Line::from(""));
+    lines.push(detail_line("Assigned to", format_opt(task.assigned_to)));
+    let assignees = task
+        .assignees
+        .as_ref()
+        .filter(|a| !a.is_empty())
+        .map(|a| {
+            a.iter()
+                .map(|assignee| {
+                    if assignee.name.is_empty() {
+                        format!("#{}", assignee.id)
+                    } else {
+                        format!("{} (#{})", assignee.name, assignee.id)
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join(", ")
+        })
+        .unwrap_or_else(|| "-".to_string());
+    lines.push(detail_line("Assignees", assignees));
+    lines.push(detail_line(
+        "Assign strategy",
+        format_opt(task.assign_strategy.as_ref()),
+    ));
+    let labels = task
+        .labels_v2
+        .as_ref()
+        .filter(|l| !l.is_empty())
+        .map(|l| l.join(", "))
+        .unwrap_or_else(|| "-".to_string());
+    lines.push(detail_line("Labels", labels));
+
+    lines.push(Line::from(""));
+    lines.push(detail_line("Notifications", format_bool(task.notification)));
+    lines.extend(json_lines(
+        "Notification metadata",
+        task.notification_metadata.as_ref(),
+        theme,
+    ));
+    lines.push(detail_line(
+        "Requires approval",
+        format_bool(task.require_approval),
+    ));
+    lines.push(detail_line("Private", format_bool(task.is_private)));
+
+    lines.push(Line::from(""));
+    lines.push(detail_line(
+        "Created",
+        format_stamp(task.created_at, task.created_by),
+    ));
+    lines.push(detail_line(
+        "Updated",
+        format_stamp(task.updated_at, task.updated_by),
+    ));
+
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled("Description", theme.label)));
+    match task.description.as_deref().filter(|d| !d.is_empty()) {
+        Some(description) => lines.extend(description.lines().map(|l| Line::from(l.to_string()))),
+        None => lines.push(Line::from("-")),
+    }
+
+    let detail = Paragraph::new(lines)
+        .block(block)
+        .wrap(Wrap { trim: false });
+    f.render_widget(detail, area);
+}
+
+fn detail_line(label: &str, value: String, theme: &Theme) -> Line<'static> {
+    Line::from(vec![
+        Span::styled(format!("{}: ", label), theme.label),
+        Span::raw(value),
+    ])
+}
+
+fn format_opt<T: ToString>(value: Option<T>) -> String {
+    value
+        .map(|v| v.to_string())
+        .unwrap_or_else(|| "-".to_string())
+}
+
+fn format_bool(value: Option<bool>) -> String {
+    match value {
+        Some(true) => "yes".to_string(),
+        Some(false) => "no".to_string(),
+        None => "-".to_string(),
+    }
+}
+
+fn format_stamp(at: Option<DateTime<Utc>>, by: Option<i64>) -> String {
+    match (at.map(dates::format_local), by) {
+        (Some(at), Some(by)) => format!("{} by #{}", at, by),
+        (Some(at), None) => at,
+        (None, Some(by)) => format!("by #{}", by),
+        (None, None) => "-".to_string(),
+    }
+}
+
+/// Renders a JSON metadata blob as an indented `key: value` outline.
+fn json_lines(label: &str, value: Option<&Value>, theme: &Theme) -> Vec<Line<'static>> {
+    match value {
+        None | Some(Value::Null) => vec![detail_line(label, "-".to_string(), theme)],
+        Some(Value::Object(map)) if map.is_empty() => {
+            vec![detail_line(label, "-".to_string(), theme)]
+        }
+        Some(value @ (Value::Object(_) | Value::Array(_))) => {
+            let mut lines = vec![Line::from(Span::styled(format!("{}:", label), theme.label))];
+            let mut outline = Vec::new();
+            outline_json(value, 1, &mut outline);
+            lines.extend(outline.into_iter().map(Line::from));
+            lines
+        }
+        Some(scalar) => vec![detail_line(label, format_json_scalar(scalar), theme)],
+    }
+}
 
-        let (status_text, status_color) = match task.status {
-            Some(1) => ("active", Color::Green),
-            Some(2) => ("completed", Color::Blue),
-            Some(0) | None => ("pending", Color::Yellow),
-            Some(_) => ("unknown", Color::White),
+fn outline_json(value: &Value, depth: usize, out: &mut Vec<String>) {
+    let indent = "  ".repeat(depth);
+    match value {
+        Value::Object(map) => {
+            for (key, value) in map {
+                match value {
+                    Value::Object(_) | Value::Array(_) if !is_empty_json(value) => {
+                        out.push(format!("{}{}:", indent, key));
+                        outline_json(value, depth + 1, out);
+                    }
+                    _ => out.push(format!("{}{}: {}", indent, key, format_json_scalar(value))),
+                }
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                match item {
+                    Value::Object(_) | Value::Array(_) if !is_empty_json(item) => {
+                        out.push(format!("{}-", indent));
+                        outline_json(item, depth + 1, out);
+                    }
+                    _ => out.push(format!("{}- {}", indent, format_json_scalar(item))),
+                }
+            }
+        }
+        scalar => out.push(format!("{}{}", indent, format_json_scalar(scalar))),
+    }
+}
+
+fn is_empty_json(value: &Value) -> bool {
+    match value {
+        Value::Object(map) => map.is_empty(),
+        Value::Array(items) => items.is_empty(),
+        _ => false,
+    }
+}
+
+fn format_json_scalar(value: &Value) -> String {
+    match value {
+        Value::Null => "-".to_string(),
+        Value::String(s) => s.clone(),
+        Value::Object(_) => "{}".to_string(),
+        Value::Array(_) => "[]".to_string(),
+        other => other.to_string(),
+    }
+}
+
+fn draw_history(f: &mut Frame, area: Rect, app: &App) {
+    let history = &app.history;
+    let title = format!("History: {}", history.chore_name);
+    let block = Block::default().borders(Borders::ALL).title(title);
+
+    if history.loading || history.entries.is_empty() {
+        let message = if history.loading {
+            "Loading history..."
+        } else {
+            "This task has not been completed yet."
         };
+        let placeholder = Paragraph::new(message)
+            .block(block)
+            .alignment(Alignment::Center);
+        f.render_widget(placeholder, area);
+        return;
+    }
+
+    // Completers are user ids; resolve names from the chore's assignees where possible
+    let assignees = app
+        .tasks
+        .iter()
+        .find(|t| t.id == history.chore_id)
+        .and_then(|t| t.assignees.as_ref());
+    let user_name = |id: i64| {
+        assignees
+            .and_then(|a| a.iter().find(|a| a.id == id && !a.name.is_empty()))
+            .map(|a| a.name.clone())
+            .unwrap_or_else(|| format!("#{}", id))
+    };
+
+    let header = Row::new(vec![
+        Cell::from("Completed").style(app.theme.header),
+        Cell::from("By").style(app.theme.header),
+        Cell::from("Notes").style(app.theme.header),
+    ]);
 
-        Row::new(vec![
-            Cell::from(task.name.clone()),
-            Cell::from(task.next_due_date.clone().unwrap_or_else(|| "-".to_string())),
-            Cell::from(status_text).style(Style::default().fg(status_color)),
-            Cell::from(task.priority.map(|p| p.to_string()).unwrap_or_else(|| "-".to_string())),
-        ]).style(style)
-    }).collect();
+    let rows: Vec<Row> = history
+        .entries
+        .iter()
+        .map(|entry| {
+            Row::new(vec![
+                Cell::from(
+                    entry
+                        .performed_at
+                        .map(dates::format_local)
+                        .unwrap_or_else(|| "-".to_string()),
+                ),
+                Cell::from(
+                    entry
+                        .completed_by
+                        .map(user_name)
+                        .unwrap_or_else(|| "-".to_string()),
+                ),
+                Cell::from(
+                    entry
+                        .notes
+                        .clone()
+                        .filter(|n| !n.is_empty())
+                        .unwrap_or_else(|| "-".to_string()),
+                ),
+            ])
+        })
+        .collect();
 
     let widths = [
-        Constraint::Percentage(40),
-        Constraint::Percentage(20),
-        Constraint::Percentage(20),
+        Constraint::Percentage(30),
         Constraint::Percentage(20),
+        Constraint::Percentage(50),
     ];
 
     let table = Table::new(rows, widths)
         .header(header)
-        .block(Block::default().borders(Borders::ALL).title("Donetick Tasks"));
+        .block(block)
+        .row_highlight_style(app.theme.selected);
 
-    f.render_widget(table, area);
+    let mut state = TableState::default().with_selected(Some(history.selected));
+    f.render_stateful_widget(table, area, &mut state);
 }
 
-fn draw_add_form(f: &mut Frame, area: Rect, app: &App) {
+fn draw_add_form(f: &mut Frame, area: Rect, app: &mut App, now: DateTime<Local>) {
+    let title = if app.view == View::EditTask {
+        "Edit Task"
+    } else {
+        "Add New Task"
+    };
+    let block = Block::default().borders(Borders::ALL).title(title);
+    f.render_widget(block, area);
+
+    let mut constraints = vec![Constraint::Length(3); FormField::ALL.len()];
+    constraints.push(Constraint::Min(0));
+
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .margin(2)
-        .constraints([
-            Constraint::Length(3),
-            Constraint::Length(3),
-            Constraint::Length(3),
-            Constraint::Min(0),
-        ])
+        .constraints(constraints)
         .split(area);
 
-    let block = Block::default().borders(Borders::ALL).title("Add New Task");
-    f.render_widget(block, area);
-
-    let name_label = Paragraph::new("Name:");
-    f.render_widget(name_label, chunks[0]);
+    for (field, chunk) in FormField::ALL.iter().zip(chunks.iter()) {
+        let style = if app.form_state.active_field == *field {
+            app.theme.highlight
+        } else {
+            Style::default()
+        };
 
-    let name_style = if app.form_state.active_field == FormField::Name {
-        Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)
-    } else {
-        Style::default()
-    };
+        let mut block = Block::default()
+            .borders(Borders::ALL)
+            .title(form_field_label(field));
+        if *field == FormField::DueDate {
+            if let Some(preview) = due_date_preview(&app.form_state.due_date, now, &app.theme) {
+                block = block.title_bottom(preview);
+            }
+        }
 
-    let name_input = Paragraph::new(app.form_state.name.clone())
-        .block(Block::default().borders(Borders::ALL))
-        .style(name_style);
-    f.render_widget(name_input, chunks[1]);
+        let input = Paragraph::new(app.form_state.field_value(field).to_string())
+            .block(block)
+            .style(style);
+        f.render_widget(input, *chunk);
+        app.regions.form_fields.push((field.clone(), *chunk));
+    }
+}
 
-    let due_date_label = Paragraph::new("Due Date (optional, YYYY-MM-DD):");
-    f.render_widget(due_date_label, chunks[2]);
+/// Shows what a typed due date resolves to before the form is submitted.
+fn due_date_preview(input: &str, now: DateTime<Local>, theme: &Theme) -> Option<Line<'static>> {
+    if input.trim().is_empty() {
+        return None;
+    }
 
-    let due_date_style = if app.form_state.active_field == FormField::DueDate {
-        Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)
-    } else {
-        Style::default()
+    let preview = match dates::parse_due_input_at(input, now) {
+        Some(due) => Span::styled(
+            format!(
+                " → {} ({}) ",
+                dates::format_local(due),
+                dates::relative_due(due, now)
+            ),
+            theme.success,
+        ),
+        None => Span::styled(" not a recognised date ", theme.error),
     };
+    Some(Line::from(preview))
+}
 
-    let due_date_input = Paragraph::new(app.form_state.due_date.clone())
-        .block(Block::default().borders(Borders::ALL))
-        .style(due_date_style);
-    f.render_widget(due_date_input, chunks[3]);
+fn form_field_label(field: &FormField) -> &'static str {
+    match field {
+        FormField::Name => "Name",
+        FormField::DueDate => {
+            "Due Date (optional, e.g. tomorrow 9am, next fri, in 3 days, eom, YYYY-MM-DD)"
+        }
+        FormField::Description => "Description (optional)",
+        FormField::Priority => "Priority (optional, 0-4)",
+        FormField::Labels => "Labels (optional, comma separated)",
+        FormField::Recurrence => {
+            "Repeats (optional: once, daily, weekly, monthly, yearly, every 3 days, mon wed fri)"
+        }
+        FormField::Rolling => "Rolling: next due counts from completion (space/y/n)",
+    }
 }
 
-fn draw_error_dialog(f: &mut Frame, area: Rect, app: &App) {
+/// Returns a centered rect of the given height spanning 60% of the width.
+fn popup_area(area: Rect, height: u16) -> Rect {
     let popup_layout = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
             Constraint::Percentage(30),
-            Constraint::Length(7),
+            Constraint::Length(height),
             Constraint::Percentage(30),
         ])
         .split(area);
@@ -153,57 +809,413 @@ fn draw_error_dialog(f: &mut Frame, area: Rect, app: &App) {
         ])
         .split(popup_layout[1]);
 
-    let error_message = app.error_message.as_ref().map(|s| s.as_str()).unwrap_or("Unknown error");
+    horizontal_layout[1]
+}
 
-    let error_text = vec![
-        Line::from(""),
-        Line::from(Span::styled("Error", Style::default().fg(Color::Red).add_modifier(Modifier::BOLD))),
+/// Height of a bordered popup that fits `lines` once wrapped.
+fn popup_height(area: Rect, lines: &[Line]) -> u16 {
+    let inner_width = (popup_area(area, 0).width.saturating_sub(2)).max(1) as usize;
+    let wrapped: usize = lines
+        .iter()
+        .map(|line| line.width().max(1).div_ceil(inner_width))
+        .sum();
+    (wrapped as u16 + 2).min(area.height)
+}
+
+fn draw_error_dialog(f: &mut Frame, area: Rect, app: &App) {
+    let error_message = app.error_message.as_deref().unwrap_or("Unknown error");
+
+    let mut error_text = vec![
         Line::from(""),
-        Line::from(error_message),
+        Line::from(Span::styled(
+            "Error",
+            app.theme.error.add_modifier(Modifier::BOLD),
+        )),
         Line::from(""),
-        Line::from(Span::styled("Press any key to continue", Style::default().fg(Color::Gray))),
     ];
+    error_text.extend(error_message.lines().map(|l| Line::from(l.to_string())));
+    if let Some(hint) = &app.error_hint {
+        error_text.push(Line::from(""));
+        error_text.push(Line::from(Span::styled(hint.clone(), app.theme.warning)));
+    }
+    error_text.push(Line::from(""));
+    error_text.push(Line::from(Span::styled(
+        "Press any key to continue",
+        app.theme.muted,
+    )));
+
+    // Grow with long and multi-line messages
+    let height = popup_height(area, &error_text);
 
     let error_block = Paragraph::new(error_text)
-        .block(Block::default().borders(Borders::ALL).style(Style::default().bg(Color::Black)))
-        .alignment(Alignment::Center);
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .style(app.theme.popup),
+        )
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true });
 
-    f.render_widget(error_block, horizontal_layout[1]);
+    let popup = popup_area(area, height);
+    f.render_widget(Clear, popup);
+    f.render_widget(error_block, popup);
 }
 
-fn draw_help_footer(f: &mut Frame, area: Rect, view: &View) {
-    let help_text = match view {
-        View::TaskList => {
-            vec![
-                Span::raw("↑/k: up | ↓/j: down | "),
-                Span::styled("Enter", Style::default().add_modifier(Modifier::BOLD)),
-                Span::raw(": complete | "),
-                Span::styled("a", Style::default().add_modifier(Modifier::BOLD)),
-                Span::raw(": add | "),
-                Span::styled("r", Style::default().add_modifier(Modifier::BOLD)),
-                Span::raw(": refresh | "),
-                Span::styled("q", Style::default().add_modifier(Modifier::BOLD)),
-                Span::raw(": quit"),
-            ]
-        }
-        View::AddTask => {
-            vec![
-                Span::styled("Tab", Style::default().add_modifier(Modifier::BOLD)),
-                Span::raw(": next field | "),
-                Span::styled("Enter", Style::default().add_modifier(Modifier::BOLD)),
-                Span::raw(": submit | "),
-                Span::styled("Esc", Style::default().add_modifier(Modifier::BOLD)),
-                Span::raw(": cancel"),
-            ]
+fn draw_confirm_dialog(f: &mut Frame, area: Rect, app: &App) {
+    let message = app
+        .confirmation
+        .as_ref()
+        .map(|c| c.message.as_str())
+        .unwrap_or("Are you sure?");
+
+    let confirm_text = vec![
+        Line::from(""),
+        Line::from(Span::styled(
+            "Confirm",
+            app.theme.warning.add_modifier(Modifier::BOLD),
+        )),
+        Line::from(""),
+        Line::from(message),
+        Line::from(""),
+        Line::from(Span::styled("y: yes | n: no", app.theme.muted)),
+    ];
+
+    let confirm_block = Paragraph::new(confirm_text)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .style(app.theme.popup),
+        )
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true });
+
+    let popup = popup_area(area, 8);
+    f.render_widget(Clear, popup);
+    f.render_widget(confirm_block, popup);
+}
+
+fn draw_profile_picker(f: &mut Frame, area: Rect, app: &App) {
+    let rows: Vec<Row> = app
+        .profiles
+        .names
+        .iter()
+        .map(|name| {
+            let marker = if app.profile.as_deref() == Some(name.as_str()) {
+                "*"
+            } else {
+                " "
+            };
+            Row::new(vec![Cell::from(marker), Cell::from(name.clone())])
+        })
+        .collect();
+
+    let table = Table::new(rows, [Constraint::Length(1), Constraint::Min(0)])
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("Switch profile")
+                .style(app.theme.popup),
+        )
+        .row_highlight_style(app.theme.selected);
+
+    let height = (app.profiles.names.len() as u16 + 2).min(area.height);
+    let popup = popup_area(area, height);
+    let mut state = TableState::default().with_selected(Some(app.profiles.selected));
+    f.render_widget(Clear, popup);
+    f.render_stateful_widget(table, popup, &mut state);
+}
+
+fn draw_bulk_prompt(f: &mut Frame, area: Rect, app: &App, now: DateTime<Local>) {
+    let prompt = &app.bulk_prompt;
+    let count = app.bulk_targets().len();
+    let (title, hint) = match prompt.field {
+        BulkField::Labels => (
+            "Relabel",
+            "kitchen, weekly replaces | +label -label adjusts",
+        ),
+        BulkField::Assignee => ("Reassign", "user id or name"),
+        BulkField::DueDate => ("Reschedule", "e.g. tomorrow 9am, next fri, in 3 days"),
+    };
+
+    let mut lines = vec![
+        Line::from(Span::styled(hint, app.theme.muted)),
+        Line::from(format!("> {}", prompt.input)),
+    ];
+    match &prompt.error {
+        Some(error) => lines.push(Line::from(Span::styled(error.clone(), app.theme.error))),
+        None if prompt.field == BulkField::DueDate => {
+            lines.extend(due_date_preview(&prompt.input, now, &app.theme));
         }
-        View::ErrorDialog => {
-            vec![Span::raw("Press any key to continue")]
+        None => {}
+    }
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!("{} {} chores", title, count))
+        .style(app.theme.popup);
+    let popup = popup_area(area, popup_height(area, &lines));
+    f.render_widget(Clear, popup);
+    f.render_widget(
+        Paragraph::new(lines)
+            .block(block)
+            .wrap(Wrap { trim: false }),
+        popup,
+    );
+}
+
+fn draw_bulk_summary(f: &mut Frame, area: Rect, app: &App) {
+    let summary = &app.bulk_summary;
+    let failed = summary.results.iter().filter(|r| r.error.is_some()).count();
+    let title = format!(
+        "{} {} of {}",
+        summary.past,
+        summary.results.len() - failed,
+        summary.results.len()
+    );
+
+    let mut lines: Vec<Line> = summary
+        .results
+        .iter()
+        .map(|result| match &result.error {
+            None => Line::from(vec![
+                Span::styled("✓ ", app.theme.success),
+                Span::raw(result.name.clone()),
+            ]),
+            Some(error) => Line::from(vec![
+                Span::styled("✗ ", app.theme.error),
+                Span::raw(format!("{}: ", result.name)),
+                Span::styled(error.clone(), app.theme.error),
+            ]),
+        })
+        .collect();
+    if failed > 0 {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            "Failed chores stay marked so you can retry them",
+            app.theme.warning,
+        )));
+    }
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(title)
+        .style(app.theme.popup);
+    let popup = popup_area(area, popup_height(area, &lines));
+    f.render_widget(Clear, popup);
+    f.render_widget(
+        Paragraph::new(lines)
+            .block(block)
+            .wrap(Wrap { trim: false }),
+        popup,
+    );
+}
+
+/// A piece of the footer: plain text, or the keys bound to an action and
+/// what it does in this view.
+enum Footer {
+    Text(String),
+    Hint(Action, &'static str),
+}
+
+fn hint(action: Action) -> Footer {
+    Footer::Hint(action, action.description())
+}
+
+/// The footer for the current view, from the keymap.
+fn footer(app: &App) -> Vec<Footer> {
+    let mut items = Vec::new();
+    if !app.pending_keys.is_empty() {
+        let keys: Vec<String> = app.pending_keys.iter().map(|k| k.to_string()).collect();
+        items.push(Footer::Text(format!("{}…", keys.concat())));
+    }
+    let hints = match app.view {
+        View::TaskList if app.search.editing => vec![
+            Footer::Text("Type to search".to_string()),
+            Footer::Hint(Action::Submit, "keep results"),
+            Footer::Hint(Action::Cancel, "clear"),
+        ],
+        View::TaskList if app.filter.editing => vec![
+            Footer::Text(
+                "status: label: assignee: active: due:<7d priority: overdue, -negates".to_string(),
+            ),
+            Footer::Hint(Action::Submit, "done"),
+            Footer::Hint(Action::Cancel, "clear"),
+        ],
+        View::TaskList if app.has_marks() => vec![
+            hint(Action::Mark),
+            Footer::Hint(Action::MarkRange, "range"),
+            hint(Action::Complete),
+            hint(Action::Delete),
+            hint(Action::Relabel),
+            hint(Action::Reassign),
+            hint(Action::Reschedule),
+            Footer::Hint(Action::Cancel, "unmark"),
+        ],
+        View::TaskList => vec![
+            hint(Action::MoveUp),
+            hint(Action::MoveDown),
+            hint(Action::Complete),
+            hint(Action::Add),
+            hint(Action::Search),
+            hint(Action::Help),
+            hint(Action::Filter),
+            hint(Action::ClearFilter),
+            hint(Action::Sort),
+            hint(Action::ReverseSort),
+            hint(Action::Edit),
+            hint(Action::Delete),
+            hint(Action::Archive),
+            hint(Action::Undo),
+            hint(Action::Details),
+            hint(Action::History),
+            hint(Action::Refresh),
+            hint(Action::Profiles),
+            hint(Action::Mark),
+            Footer::Hint(Action::MarkRange, "range"),
+            hint(Action::Quit),
+        ],
+        View::AddTask | View::EditTask => vec![
+            hint(Action::NextField),
+            hint(Action::Submit),
+            hint(Action::Cancel),
+        ],
+        View::ErrorDialog | View::BulkSummary => {
+            vec![Footer::Text("Press any key to continue".to_string())]
         }
+        View::History => vec![
+            hint(Action::MoveUp),
+            hint(Action::MoveDown),
+            Footer::Hint(Action::Cancel, "back"),
+        ],
+        View::Profiles => vec![
+            hint(Action::MoveUp),
+            hint(Action::MoveDown),
+            Footer::Hint(Action::Complete, "switch"),
+            Footer::Hint(Action::Cancel, "back"),
+        ],
+        View::BulkEdit => vec![Footer::Hint(Action::Submit, "apply"), hint(Action::Cancel)],
+        View::Confirm => vec![
+            Footer::Hint(Action::Submit, "confirm"),
+            hint(Action::Cancel),
+        ],
+        View::Help => vec![
+            Footer::Hint(Action::MoveUp, "scroll up"),
+            Footer::Hint(Action::MoveDown, "scroll down"),
+            Footer::Hint(Action::Cancel, "close"),
+        ],
     };
+    items.extend(hints);
+    items
+}
 
-    let help = Paragraph::new(Line::from(help_text))
-        .style(Style::default().bg(Color::DarkGray))
+fn draw_help_footer(f: &mut Frame, area: Rect, app: &mut App) {
+    let key_style = app.theme.footer_key;
+    let mut spans = Vec::new();
+    // Each hint's start and end column within the line
+    let mut hints = Vec::new();
+    let mut width = 0;
+    for item in footer(app) {
+        let (key, text) = match item {
+            Footer::Text(text) => (None, text),
+            Footer::Hint(action, description) => {
+                let label = app.keymap.label(action);
+                // Unbound actions have nothing to show
+                if label.is_empty() {
+                    continue;
+                }
+                (Some((action, label)), format!(": {}", description))
+            }
+        };
+        if !spans.is_empty() {
+            spans.push(Span::raw(" | "));
+            width += 3;
+        }
+        let start = width;
+        if let Some((action, label)) = key {
+            width += Line::raw(label.as_str()).width() + Line::raw(text.as_str()).width();
+            hints.push((start, width, action));
+            spans.push(Span::styled(label, key_style));
+        } else {
+            width += Line::raw(text.as_str()).width();
+        }
+        spans.push(Span::raw(text));
+    }
+
+    // Same offset the paragraph uses; overlong lines are cut at the right
+    let shown = (width as u16).min(area.width);
+    let left = area.x + (area.width / 2).saturating_sub(shown / 2);
+    let right = area.x + area.width;
+    app.regions.hints = hints
+        .into_iter()
+        .map(|(start, end, action)| {
+            (
+                left.saturating_add(start as u16),
+                left.saturating_add(end as u16),
+                action,
+            )
+        })
+        .filter(|(start, _, _)| *start < right)
+        .map(|(start, end, action)| (Rect::new(start, area.y, end.min(right) - start, 1), action))
+        .collect();
+
+    let help = Paragraph::new(Line::from(spans))
+        .style(app.theme.footer)
         .alignment(Alignment::Center);
 
     f.render_widget(help, area);
 }
+
+/// Every binding in the keymap, task list first, then inputs.
+fn draw_help(f: &mut Frame, area: Rect, app: &mut App) {
+    let sections = [
+        ("Task list, history and profiles", Context::List),
+        ("Forms, search, filter and prompts", Context::Input),
+    ];
+    let keys = |action: Action| {
+        let keys: Vec<String> = app
+            .keymap
+            .keys(action)
+            .iter()
+            .map(|k| k.to_string())
+            .collect();
+        keys.join(", ")
+    };
+    let column = Action::ALL
+        .iter()
+        .map(|&a| Line::raw(keys(a)).width())
+        .max()
+        .unwrap_or(0)
+        + 2;
+
+    let mut lines = Vec::new();
+    for (title, context) in sections {
+        if !lines.is_empty() {
+            lines.push(Line::from(""));
+        }
+        lines.push(Line::from(Span::styled(title, app.theme.title)));
+        for action in Action::ALL.into_iter().filter(|a| a.in_context(context)) {
+            let keys = keys(action);
+            let padding = " ".repeat(column.saturating_sub(Line::raw(keys.as_str()).width()));
+            lines.push(Line::from(vec![
+                Span::styled(format!("  {}", keys), app.theme.key),
+                Span::raw(padding),
+                Span::raw(action.description()),
+            ]));
+        }
+    }
+    lines.push(Line::from(""));
+    lines.push(Line::from(
+        "Other keys type into the active input; y and n also answer confirmations.",
+    ));
+
+    // Stop once the last line is on screen
+    let inner_height = area.height.saturating_sub(2);
+    let last = (lines.len() as u16).saturating_sub(inner_height);
+    app.help_scroll = app.help_scroll.min(last);
+
+    let block = Block::default().borders(Borders::ALL).title("Keys");
+    let help = Paragraph::new(lines)
+        .block(block)
+        .scroll((app.help_scroll, 0));
+    f.render_widget(help, area);
+}
@@ -0,0 +1,197 @@
+use crate::api::error::ApiError;
+use crate::api::{Chore, ChoreApi, CreateChoreRequest, UpdateChoreRequest};
+
+/// Actions kept for undo. Older ones are dropped first.
+const DEPTH: usize = 20;
+
+/// A change that `u` can take back, with the state needed to do it.
+#[derive(Debug, Clone)]
+pub struct Undo {
+    /// Past tense of the change, e.g. "Completed"
+    pub verb: &'static str,
+    /// What it applied to: a chore name or "3 chores"
+    pub subject: String,
+    pub reverts: Vec<Revert>,
+}
+
+/// How to put one chore back the way it was.
+#[derive(Debug, Clone)]
+pub enum Revert {
+    /// Moves the due date back to before the completion and reactivates
+    /// one-off chores, which the server deactivates on completion. A chore
+    /// deleted since is created again. The completion itself stays in the
+    /// chore's history.
+    Complete(Chore),
+    /// Creates the chore again from the copy taken before the delete. It
+    /// comes back with a new id and without its history.
+    Delete(Chore),
+    /// Sends every editable field of the copy taken before the edit.
+    Edit(Chore),
+    Archive {
+        id: i64,
+        was_active: bool,
+    },
+    Create(i64),
+}
+
+impl Undo {
+    pub fn new(verb: &'static str, chore: &Chore, revert: Revert) -> Self {
+        Undo {
+            verb,
+            subject: chore.name.clone(),
+            reverts: vec![revert],
+        }
+    }
+
+    /// Groups the reverts of a bulk action, which is undone as a whole.
+    pub fn bulk(verb: &'static str, reverts: Vec<Revert>) -> Self {
+        Undo {
+            verb,
+            subject: format!("{} chores", reverts.len()),
+            reverts,
+        }
+    }
+
+    pub fn completed(chore: &Chore) -> Self {
+        Undo::new("Completed", chore, Revert::Complete(chore.clone()))
+    }
+
+    pub fn deleted(chore: &Chore) -> Self {
+        Undo::new("Deleted", chore, Revert::Delete(chore.clone()))
+    }
+
+    pub fn edited(chore: &Chore) -> Self {
+        Undo::new("Edited", chore, Revert::Edit(chore.clone()))
+    }
+
+    pub fn archived(chore: &Chore, active: bool) -> Self {
+        let verb = if active { "Restored" } else { "Archived" };
+        let revert = Revert::Archive {
+            id: chore.id,
+            was_active: chore.is_active.unwrap_or(true),
+        };
+        Undo::new(verb, chore, revert)
+    }
+
+    pub fn created(chore: &Chore) -> Self {
+        Undo::new("Added", chore, Revert::Create(chore.id))
+    }
+
+    /// The toast shown after the change, e.g. "Completed Dishes — press u to undo".
+    pub fn toast(&self) -> String {
+        format!("{} {} — press u to undo", self.verb, self.subject)
+    }
+}
+
+/// Most recent change last.
+#[derive(Debug, Default)]
+pub struct UndoStack {
+    entries: Vec<Undo>,
+}
+
+impl UndoStack {
+    pub fn push(&mut self, undo: Undo) {
+        if self.entries.len() == DEPTH {
+            self.entries.remove(0);
+        }
+        self.entries.push(undo);
+    }
+
+    pub fn pop(&mut self) -> Option<Undo> {
+        self.entries.pop()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
+
+/// Reverts every chore in `undo`, carrying on past failures so one missing
+/// chore does not stop the rest. On failure returns `undo` cut down to the
+/// reverts that failed, so it can be tried again, with the last error.
+pub async fn revert<A: ChoreApi>(client: &A, undo: &Undo) -> Result<(), (Undo, ApiError)> {
+    let mut failed = Vec::new();
+    let mut error = None;
+    for revert in &undo.reverts {
+        if let Err(e) = revert_one(client, revert).await {
+            failed.push(revert.clone());
+            error = Some(e);
+        }
+    }
+    match error {
+        None => Ok(()),
+        Some(e) => Err((
+            Undo {
+                reverts: failed,
+                ..undo.clone()
+            },
+            e,
+        )),
+    }
+}
+
+async fn revert_one<A: ChoreApi>(client: &A, revert: &Revert) -> Result<(), ApiError> {
+    match revert {
+        Revert::Complete(chore) => {
+            let request = UpdateChoreRequest {
+                due_date: Some(chore.next_due_date),
+                ..UpdateChoreRequest::default()
+            };
+            match client.update_chore(chore.id, request).await {
+                Ok(updated) => {
+                    // Unless the server says it is still active
+                    if chore.is_active != Some(false) && updated.is_active != Some(true) {
+                        client.set_chore_active(chore.id, true).await?;
+                    }
+                    Ok(())
+                }
+                Err(ApiError::NotFound(_)) => recreate(client, chore).await,
+                Err(e) => Err(e),
+            }
+        }
+        Revert::Delete(chore) => recreate(client, chore).await,
+        Revert::Edit(chore) => client
+            .update_chore(chore.id, restore_request(chore))
+            .await
+            .map(|_| ()),
+        Revert::Archive { id, was_active } => client.set_chore_active(*id, *was_active).await,
+        Revert::Create(id) => client.delete_chore(*id).await,
+    }
+}
+
+async fn recreate<A: ChoreApi>(client: &A, chore: &Chore) -> Result<(), ApiError> {
+    let request = CreateChoreRequest {
+        name: chore.name.clone(),
+        due_date: chore.next_due_date,
+        description: chore.description.clone(),
+        priority: chore.priority,
+        labels: chore.labels_v2.clone(),
+        frequency_type: chore.frequency_type.clone(),
+        frequency: chore.frequency,
+        frequency_metadata: chore.frequency_metadata.clone(),
+        is_rolling: chore.is_rolling,
+    };
+    client.create_chore(request).await.map(|_| ())
+}
+
+/// An update that sets every editable field back to `chore`'s values. The
+/// due date, description, priority and labels are cleared if it had none;
+/// the recurrence and assignee are only sent when it had them.
+fn restore_request(chore: &Chore) -> UpdateChoreRequest {
+    UpdateChoreRequest {
+        name: Some(chore.name.clone()),
+        due_date: Some(chore.next_due_date),
+        description: Some(chore.description.clone().unwrap_or_default()),
+        priority: Some(chore.priority.unwrap_or(0)),
+        labels: Some(chore.labels_v2.clone().unwrap_or_default()),
+        frequency_type: chore.frequency_type.clone(),
+        frequency: chore.frequency,
+        frequency_metadata: chore.frequency_metadata.clone(),
+        is_rolling: chore.is_rolling,
+        assigned_to: chore.assigned_to,
+    }
+}
@@ -0,0 +1,133 @@
+mod common;
+
+use common::{chore, Call, FakeApi};
+use donetick_tui::api::Chore;
+use donetick_tui::bulk::{self, BulkAction, LabelEdit};
+use std::sync::Mutex;
+
+fn labels(names: &[&str]) -> Vec<String> {
+    names.iter().map(|n| n.to_string()).collect()
+}
+
+#[test]
+fn plain_label_list_replaces() {
+    let edit = LabelEdit::parse("kitchen, weekly").unwrap();
+
+    assert_eq!(
+        edit.apply(&labels(&["garden"])),
+        labels(&["kitchen", "weekly"])
+    );
+}
+
+#[test]
+fn empty_label_list_clears() {
+    let edit = LabelEdit::parse("").unwrap();
+
+    assert!(edit.apply(&labels(&["garden"])).is_empty());
+}
+
+#[test]
+fn plus_and_minus_adjust_labels() {
+    let edit = LabelEdit::parse("+weekly -Upstairs +kitchen").unwrap();
+
+    // Case-insensitive, and an existing label is not added twice
+    assert_eq!(
+        edit.apply(&labels(&["upstairs", "Kitchen"])),
+        labels(&["Kitchen", "weekly"])
+    );
+}
+
+#[test]
+fn mixed_label_styles_are_rejected() {
+    assert!(LabelEdit::parse("kitchen +weekly").is_err());
+}
+
+#[tokio::test]
+async fn results_follow_input_order() {
+    let chores: Vec<_> = (1..=9)
+        .map(|id| chore(id, &format!("Chore {}", id)))
+        .collect();
+    let api = FakeApi::with_chores(chores.clone());
+
+    let results = bulk::run(&api, BulkAction::Complete, chores, |_, _| {}).await;
+
+    let ids: Vec<i64> = results.iter().map(|r| r.id).collect();
+    assert_eq!(ids, (1..=9).collect::<Vec<_>>());
+    assert!(results.iter().all(|r| r.error.is_none()));
+    assert_eq!(api.calls().len(), 9);
+    // One-off chores are deactivated rather than removed
+    let chores = &api.store().chores;
+    assert!(chores.iter().all(|c| c.is_active == Some(false)));
+}
+
+#[tokio::test]
+async fn failures_are_reported_per_chore() {
+    let api = FakeApi::with_chores(vec![chore(1, "Dishes"), chore(3, "Vacuum")]);
+    let chores = vec![
+        chore(1, "Dishes"),
+        chore(2, "Laundry"),
+        chore(3, "Vacuum"),
+        chore(-1, "Queued offline"),
+    ];
+
+    let results = bulk::run(&api, BulkAction::Delete, chores, |_, _| {}).await;
+
+    let failed: Vec<&str> = results
+        .iter()
+        .filter(|r| r.error.is_some())
+        .map(|r| r.name.as_str())
+        .collect();
+    assert_eq!(failed, ["Laundry", "Queued offline"]);
+    assert!(api.store().chores.is_empty());
+    // The unsynced chore never reaches the server
+    assert!(!api.calls().contains(&Call::Delete(-1)));
+}
+
+#[tokio::test]
+async fn progress_counts_up_to_total() {
+    let chores = vec![chore(1, "Dishes"), chore(2, "Laundry"), chore(3, "Vacuum")];
+    let api = FakeApi::with_chores(chores.clone());
+    let seen = Mutex::new(Vec::new());
+
+    bulk::run(&api, BulkAction::Complete, chores, |done, total| {
+        seen.lock().unwrap().push((done, total));
+    })
+    .await;
+
+    assert_eq!(seen.into_inner().unwrap(), [(1, 3), (2, 3), (3, 3)]);
+}
+
+#[tokio::test]
+async fn field_edits_update_each_chore() {
+    let chores = vec![
+        Chore {
+            labels_v2: Some(labels(&["kitchen"])),
+            ..chore(1, "Dishes")
+        },
+        chore(2, "Laundry"),
+    ];
+    let api = FakeApi::with_chores(chores.clone());
+
+    let relabel = BulkAction::Relabel(LabelEdit::parse("+weekly").unwrap());
+    bulk::run(&api, relabel, chores.clone(), |_, _| {}).await;
+    bulk::run(&api, BulkAction::Reassign(8), chores.clone(), |_, _| {}).await;
+    bulk::run(
+        &api,
+        BulkAction::Reschedule(common::at(10)),
+        chores,
+        |_, _| {},
+    )
+    .await;
+
+    let store = api.store();
+    assert_eq!(
+        store.chores[0].labels_v2,
+        Some(labels(&["kitchen", "weekly"]))
+    );
+    assert_eq!(store.chores[1].labels_v2, Some(labels(&["weekly"])));
+    assert!(store.chores.iter().all(|c| c.assigned_to == Some(8)));
+    assert!(store
+        .chores
+        .iter()
+        .all(|c| c.next_due_date == Some(common::at(10))));
+}
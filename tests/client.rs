@@ -0,0 +1,266 @@
+mod common;
+
+use common::{chore, FakeApi, ListFormat, MockServer, TOKEN};
+use donetick_tui::api::error::ApiError;
+use donetick_tui::api::{ApiClient, ClientSettings, CreateChoreRequest, UpdateChoreRequest};
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+
+async fn server(format: ListFormat) -> (FakeApi, MockServer) {
+    let api = FakeApi::with_chores(vec![chore(1, "Dishes"), chore(2, "Laundry")]);
+    let server = MockServer::start(api.clone(), format).await;
+    (api, server)
+}
+
+fn create(name: &str) -> CreateChoreRequest {
+    CreateChoreRequest {
+        name: name.to_string(),
+        due_date: Some(common::at(3)),
+        description: None,
+        priority: Some(2),
+        labels: Some(vec!["kitchen".to_string()]),
+        frequency_type: None,
+        frequency: None,
+        frequency_metadata: None,
+        is_rolling: None,
+    }
+}
+
+#[tokio::test]
+async fn lists_wrapped_response() {
+    let (_, server) = server(ListFormat::Wrapped).await;
+
+    let chores = server.client().list_chores().await.unwrap();
+
+    let names: Vec<_> = chores.iter().map(|c| c.name.as_str()).collect();
+    assert_eq!(names, ["Dishes", "Laundry"]);
+    assert_eq!(chores[0].next_due_date, Some(common::at(1)));
+}
+
+#[tokio::test]
+async fn lists_bare_array_response() {
+    let (_, server) = server(ListFormat::Bare).await;
+
+    let chores = server.client().list_chores().await.unwrap();
+
+    assert_eq!(chores.len(), 2);
+    assert_eq!(chores[1].id, 2);
+}
+
+#[tokio::test]
+async fn sends_token_header() {
+    let (_, server) = server(ListFormat::Wrapped).await;
+
+    server.client().list_chores().await.unwrap();
+
+    let request = &server.requests()[0];
+    assert_eq!(request.method, "GET");
+    assert_eq!(request.path, "/eapi/v1/chore");
+    assert_eq!(
+        request.headers.get("secretkey").map(String::as_str),
+        Some(TOKEN)
+    );
+}
+
+#[tokio::test]
+async fn wrong_token_is_unauthorized() {
+    let (_, server) = server(ListFormat::Wrapped).await;
+    let client = ApiClient::new(
+        server.url().to_string(),
+        "wrong".to_string(),
+        ClientSettings::default(),
+    )
+    .unwrap();
+
+    let error = client.list_chores().await.unwrap_err();
+
+    assert!(matches!(&error, ApiError::Unauthorized(msg) if msg == "invalid secret key"));
+    assert!(error.hint().is_some());
+}
+
+#[tokio::test]
+async fn history_in_both_formats() {
+    for format in [ListFormat::Wrapped, ListFormat::Bare] {
+        let (_, server) = server(format).await;
+        let client = server.client();
+        let mut recurring = create("Plants");
+        recurring.frequency_type = Some("daily".to_string());
+        recurring.frequency = Some(1);
+        let id = client.create_chore(recurring).await.unwrap().id;
+        client.complete_chore(id).await.unwrap();
+        client.complete_chore(id).await.unwrap();
+
+        let history = client.chore_history(id).await.unwrap();
+
+        assert_eq!(history.len(), 2, "{:?}", format);
+        assert!(history.iter().all(|entry| entry.chore_id == id));
+    }
+}
+
+#[tokio::test]
+async fn create_update_and_list() {
+    let (api, server) = server(ListFormat::Wrapped).await;
+    let client = server.client();
+
+    let created = client.create_chore(create("Vacuum")).await.unwrap();
+    assert_eq!(created.id, 3);
+    assert_eq!(created.labels_v2, Some(vec!["kitchen".to_string()]));
+
+    let body: serde_json::Value = serde_json::from_str(&server.requests()[0].body).unwrap();
+    assert_eq!(body["Name"], "Vacuum");
+    assert_eq!(body["Priority"], 2);
+
+    let update = UpdateChoreRequest {
+        name: Some("Vacuum upstairs".to_string()),
+        ..UpdateChoreRequest::default()
+    };
+    let updated = client.update_chore(3, update).await.unwrap();
+    assert_eq!(updated.name, "Vacuum upstairs");
+    // Fields missing from a partial update are left alone
+    assert_eq!(updated.priority, Some(2));
+    assert_eq!(updated.next_due_date, Some(common::at(3)));
+
+    let clear = UpdateChoreRequest {
+        due_date: Some(None),
+        ..UpdateChoreRequest::default()
+    };
+    let cleared = client.update_chore(3, clear).await.unwrap();
+    let body: serde_json::Value = serde_json::from_str(&server.requests()[2].body).unwrap();
+    assert_eq!(body, serde_json::json!({ "DueDate": null }));
+    assert_eq!(cleared.next_due_date, None);
+
+    let names: Vec<_> = client
+        .list_chores()
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|c| c.name)
+        .collect();
+    assert_eq!(names, ["Dishes", "Laundry", "Vacuum upstairs"]);
+    assert_eq!(api.store().chores.len(), 3);
+}
+
+#[tokio::test]
+async fn complete_delete_and_archive() {
+    let (api, server) = server(ListFormat::Wrapped).await;
+    let client = server.client();
+
+    client.complete_chore(1).await.unwrap();
+    assert_eq!(api.store().chores[0].is_active, Some(false));
+    client.set_chore_active(2, false).await.unwrap();
+    assert_eq!(api.store().chores[1].is_active, Some(false));
+    client.set_chore_active(2, true).await.unwrap();
+    client.delete_chore(2).await.unwrap();
+
+    let ids: Vec<_> = api.store().chores.iter().map(|c| c.id).collect();
+    assert_eq!(ids, [1]);
+    let paths: Vec<_> = server
+        .requests()
+        .iter()
+        .map(|r| format!("{} {}", r.method, r.path))
+        .collect();
+    assert_eq!(
+        paths,
+        [
+            "POST /eapi/v1/chore/1/complete",
+            "PUT /eapi/v1/chore/2/archive",
+            "PUT /eapi/v1/chore/2/unarchive",
+            "DELETE /eapi/v1/chore/2",
+        ]
+    );
+}
+
+#[tokio::test]
+async fn missing_chore_is_not_found() {
+    let (_, server) = server(ListFormat::Wrapped).await;
+
+    let error = server.client().delete_chore(42).await.unwrap_err();
+
+    assert!(matches!(error, ApiError::NotFound(msg) if msg == "chore 42 not found"));
+}
+
+#[tokio::test]
+async fn validation_errors_keep_fields() {
+    let (_, server) = server(ListFormat::Wrapped).await;
+    server.respond_with(
+        422,
+        r#"{"error": "invalid chore", "errors": {"name": ["is required"]}}"#,
+    );
+
+    let error = server.client().create_chore(create("")).await.unwrap_err();
+
+    let ApiError::Validation { message, fields } = error else {
+        panic!("expected a validation error, got {:?}", error);
+    };
+    assert_eq!(message, "invalid chore");
+    assert_eq!(fields[0].field, "name");
+    assert_eq!(fields[0].message, "is required");
+}
+
+#[tokio::test]
+async fn html_body_is_a_decode_error() {
+    let (_, server) = server(ListFormat::Wrapped).await;
+    server.respond_with(200, "<html><body>Login</body></html>");
+
+    let error = server.client().list_chores().await.unwrap_err();
+
+    assert!(matches!(&error, ApiError::Decode { excerpt, .. } if excerpt.starts_with("<html>")));
+}
+
+#[tokio::test]
+async fn reads_retry_server_errors() {
+    let (_, server) = server(ListFormat::Wrapped).await;
+    server.respond_with(503, "");
+    server.respond_with_headers(429, &[("Retry-After", "0")], "");
+    let retries = Arc::new(AtomicU32::new(0));
+    let counter = retries.clone();
+    let client = server.client().on_retry(move |_, _, _| {
+        counter.fetch_add(1, Ordering::SeqCst);
+    });
+
+    let chores = client.list_chores().await.unwrap();
+
+    assert_eq!(chores.len(), 2);
+    assert_eq!(retries.load(Ordering::SeqCst), 2);
+    assert_eq!(server.requests().len(), 3);
+}
+
+#[tokio::test]
+async fn reads_give_up_after_max_attempts() {
+    let (_, server) = server(ListFormat::Wrapped).await;
+    for _ in 0..5 {
+        server.respond_with(500, r#"{"error": "database is locked"}"#);
+    }
+
+    let error = server.client().list_chores().await.unwrap_err();
+
+    assert!(matches!(error, ApiError::ServerError(500, msg) if msg == "database is locked"));
+    assert_eq!(server.requests().len(), 5);
+}
+
+#[tokio::test]
+async fn writes_are_not_retried() {
+    let (api, server) = server(ListFormat::Wrapped).await;
+    server.respond_with(503, "");
+
+    let error = server.client().complete_chore(1).await.unwrap_err();
+
+    assert!(matches!(error, ApiError::ServerError(503, _)));
+    assert_eq!(server.requests().len(), 1);
+    assert_eq!(api.store().chores.len(), 2);
+}
+
+#[tokio::test]
+async fn unreachable_server_is_offline() {
+    // Bind and drop a listener to get a port nothing is listening on
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    drop(listener);
+    let mut settings = ClientSettings::default();
+    settings.retry.max_attempts = 1;
+    let client = ApiClient::new(url, TOKEN.to_string(), settings).unwrap();
+
+    let error = client.list_chores().await.unwrap_err();
+
+    assert!(error.is_offline(), "{:?}", error);
+}
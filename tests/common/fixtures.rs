@@ -0,0 +1,226 @@
+//! Chore lists and app states for the UI snapshot tests. Due dates are
+//! offsets from `now()` so relative dates like "in 3 days" never drift.
+//! They stay between mid-November and early February, where no common time
+//! zone changes its UTC offset, so absolute local times match everywhere.
+
+use chrono::{DateTime, Duration, Local, TimeZone, Utc};
+use donetick_tui::api::types::Assignee;
+use donetick_tui::api::{Chore, ChoreHistory, CreateChoreRequest};
+use donetick_tui::app::App;
+use donetick_tui::offline::Operation;
+use serde_json::json;
+
+/// The moment every snapshot is rendered at.
+pub fn now() -> DateTime<Local> {
+    Local.with_ymd_and_hms(2025, 1, 15, 12, 0, 0).unwrap()
+}
+
+fn due_in(offset: Duration) -> Option<DateTime<Utc>> {
+    Some((now() + offset).to_utc())
+}
+
+fn fixture(id: i64, name: &str, due: Duration) -> Chore {
+    Chore {
+        id,
+        name: name.to_string(),
+        next_due_date: due_in(due),
+        is_active: Some(true),
+        status: Some(0),
+        ..Chore::default()
+    }
+}
+
+fn assignee(id: i64, name: &str) -> Option<Vec<Assignee>> {
+    Some(vec![Assignee {
+        id,
+        name: name.to_string(),
+    }])
+}
+
+/// One chore for each status the list can show, plus an overdue one.
+pub fn every_status() -> Vec<Chore> {
+    vec![
+        Chore {
+            frequency_type: Some("weekly".to_string()),
+            frequency: Some(1),
+            priority: Some(2),
+            assignees: assignee(7, "Sam"),
+            assigned_to: Some(7),
+            ..fixture(1, "Take out the trash", Duration::hours(2))
+        },
+        Chore {
+            status: Some(1),
+            frequency_type: Some("daily".to_string()),
+            frequency: Some(1),
+            ..fixture(2, "Water plants", Duration::days(3))
+        },
+        Chore {
+            status: Some(2),
+            frequency_type: Some("monthly".to_string()),
+            frequency: Some(1),
+            ..fixture(3, "Pay rent", Duration::days(20))
+        },
+        Chore {
+            is_active: Some(false),
+            ..fixture(4, "Clean gutters", -Duration::days(30))
+        },
+        Chore {
+            status: Some(7),
+            ..fixture(5, "Descale kettle", Duration::days(5))
+        },
+        Chore {
+            priority: Some(4),
+            ..fixture(6, "Call the plumber", -Duration::days(2))
+        },
+        Chore {
+            next_due_date: None,
+            ..fixture(7, "Sort the spice rack", Duration::zero())
+        },
+    ]
+}
+
+/// Names far wider than any column.
+pub fn long_names() -> Vec<Chore> {
+    vec![
+        Chore {
+            description: Some(
+                "Start with the top shelf and work down. Keep the screws that fit the \
+                 old cabinet hinges; everything else can go to the recycling centre."
+                    .to_string(),
+            ),
+            labels_v2: Some(vec!["garage".to_string(), "weekend".to_string()]),
+            ..fixture(
+                1,
+                "Reorganise the garage shelves, sort the screws by size and label every single drawer",
+                Duration::days(1),
+            )
+        },
+        fixture(
+            2,
+            "Renew the car insurance before the policy lapses at the end of the month",
+            Duration::days(12),
+        ),
+        fixture(3, "Supercalifragilisticexpialidociousnessly", Duration::hours(5)),
+    ]
+}
+
+/// Accented, CJK, Greek and emoji names, including double-width characters.
+pub fn unicode() -> Vec<Chore> {
+    vec![
+        Chore {
+            assignees: assignee(1, "Zoë"),
+            assigned_to: Some(1),
+            ..fixture(1, "Wäsche waschen", Duration::hours(20))
+        },
+        Chore {
+            assignees: assignee(2, "山田"),
+            assigned_to: Some(2),
+            ..fixture(2, "掃除機をかける", Duration::days(2))
+        },
+        fixture(3, "Ménage de printemps ✨", Duration::days(4)),
+        fixture(4, "Ρύθμιση θερμοστάτη", Duration::days(6)),
+        fixture(5, "🪴 Repot the monstera 🪴", -Duration::hours(3)),
+    ]
+}
+
+/// More chores than fit on any of the snapshot terminals.
+pub fn many(count: i64) -> Vec<Chore> {
+    (1..=count)
+        .map(|n| fixture(n, &format!("Chore {:02}", n), Duration::hours(n * 6)))
+        .collect()
+}
+
+/// A chore shared by several people, assigned to one of them this time.
+pub fn shared() -> Vec<Chore> {
+    vec![
+        Chore {
+            assignees: Some(vec![
+                Assignee {
+                    id: 7,
+                    name: "Sam".to_string(),
+                },
+                Assignee {
+                    id: 8,
+                    name: "Alex".to_string(),
+                },
+            ]),
+            assigned_to: Some(7),
+            ..fixture(1, "Mow the lawn", Duration::days(2))
+        },
+        fixture(2, "Defrost the freezer", Duration::days(5)),
+    ]
+}
+
+/// A chore with every detail field filled in, for the details pane.
+pub fn detailed() -> Vec<Chore> {
+    let created = (now() - Duration::days(60)).to_utc();
+    vec![Chore {
+        description: Some("Both bathrooms.\nUse the blue cloths.".to_string()),
+        priority: Some(3),
+        labels_v2: Some(vec!["cleaning".to_string(), "upstairs".to_string()]),
+        assignees: assignee(7, "Sam"),
+        assigned_to: Some(7),
+        assign_strategy: Some("least_completed".to_string()),
+        frequency_type: Some("days_of_the_week".to_string()),
+        frequency: Some(1),
+        frequency_metadata: Some(json!({ "days": ["monday", "thursday"], "time": "09:00" })),
+        is_rolling: Some(false),
+        notification: Some(true),
+        require_approval: Some(false),
+        is_private: Some(false),
+        created_at: Some(created),
+        created_by: Some(7),
+        updated_at: Some(created + Duration::days(30)),
+        updated_by: Some(8),
+        ..fixture(1, "Clean the bathrooms", Duration::days(1))
+    }]
+}
+
+pub fn history(chore_id: i64) -> Vec<ChoreHistory> {
+    (1..=3)
+        .map(|week| ChoreHistory {
+            id: week,
+            chore_id,
+            performed_at: Some((now() - Duration::weeks(week)).to_utc()),
+            completed_by: Some(if week == 2 { 8 } else { 7 }),
+            assigned_to: Some(7),
+            notes: (week == 1).then(|| "Ran out of bin bags".to_string()),
+            due_date: None,
+        })
+        .collect()
+}
+
+/// An app showing `chores`, as after a successful load.
+pub fn app_with(chores: Vec<Chore>) -> App {
+    let mut app = App::new();
+    app.set_tasks(chores);
+    app
+}
+
+/// Like `app_with`, with a completion and a new chore waiting in the
+/// offline outbox. The outbox is filled directly so nothing touches disk.
+pub fn app_with_queued(chores: Vec<Chore>) -> App {
+    let mut app = App::new();
+    let first = &chores[0];
+    app.outbox.push(Operation::Complete {
+        chore_id: first.id,
+        name: first.name.clone(),
+        due: first.next_due_date,
+    });
+    app.outbox.push(Operation::Create {
+        request: CreateChoreRequest {
+            name: "Buy bin bags".to_string(),
+            due_date: None,
+            description: None,
+            priority: None,
+            labels: None,
+            frequency_type: None,
+            frequency: None,
+            frequency_metadata: None,
+            is_rolling: None,
+        },
+    });
+    app.offline = true;
+    app.set_tasks(chores);
+    app
+}
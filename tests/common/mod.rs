@@ -0,0 +1,511 @@
+//! Test doubles for the Donetick API: an in-memory `FakeApi` for driving the
+//! event handlers, and a `MockServer` that serves the same store over HTTP
+//! for exercising `ApiClient`. `fixtures` holds the chore lists and app
+//! states rendered by the UI snapshot tests.
+
+// Each test binary uses a different subset of these helpers
+#![allow(dead_code)]
+
+pub mod fixtures;
+
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use donetick_tui::api::error::ApiError;
+use donetick_tui::api::{
+    ApiClient, Chore, ChoreApi, ChoreHistory, ClientSettings, CreateChoreRequest,
+    UpdateChoreRequest,
+};
+use donetick_tui::event::{AppEvent, EventHandler};
+use reqwest::StatusCode;
+use serde_json::json;
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex, MutexGuard};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+pub const TOKEN: &str = "test-token";
+
+/// A fixed point in time so due dates in fixtures do not drift.
+pub fn at(days: i64) -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2025, 1, 1, 9, 0, 0).unwrap() + Duration::days(days)
+}
+
+pub fn chore(id: i64, name: &str) -> Chore {
+    Chore {
+        id,
+        name: name.to_string(),
+        next_due_date: Some(at(id)),
+        is_active: Some(true),
+        status: Some(0),
+        ..Chore::default()
+    }
+}
+
+/// A call made through `FakeApi`, recorded for assertions.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Call {
+    List,
+    History(i64),
+    Create(String),
+    Update(i64),
+    Complete(i64),
+    Delete(i64),
+    SetActive(i64, bool),
+}
+
+/// Chores held in memory and the rules for changing them, shared by the
+/// fake and the mock server.
+#[derive(Debug, Default)]
+pub struct Store {
+    pub chores: Vec<Chore>,
+    pub history: HashMap<i64, Vec<ChoreHistory>>,
+    pub calls: Vec<Call>,
+    /// Returned by the next call instead of touching the store
+    pub fail_next: Option<ApiError>,
+    next_id: i64,
+}
+
+impl Store {
+    fn take_failure(&mut self) -> Result<(), ApiError> {
+        match self.fail_next.take() {
+            Some(error) => Err(error),
+            None => Ok(()),
+        }
+    }
+
+    fn find(&mut self, id: i64) -> Result<&mut Chore, ApiError> {
+        self.chores
+            .iter_mut()
+            .find(|chore| chore.id == id)
+            .ok_or_else(|| ApiError::NotFound(format!("chore {} not found", id)))
+    }
+
+    fn list(&mut self) -> Result<Vec<Chore>, ApiError> {
+        self.calls.push(Call::List);
+        self.take_failure()?;
+        Ok(self.chores.clone())
+    }
+
+    fn history(&mut self, id: i64) -> Result<Vec<ChoreHistory>, ApiError> {
+        self.calls.push(Call::History(id));
+        self.take_failure()?;
+        self.find(id)?;
+        Ok(self.history.get(&id).cloned().unwrap_or_default())
+    }
+
+    fn create(&mut self, request: CreateChoreRequest) -> Result<Chore, ApiError> {
+        self.calls.push(Call::Create(request.name.clone()));
+        self.take_failure()?;
+        let id = self
+            .chores
+            .iter()
+            .map(|c| c.id)
+            .max()
+            .unwrap_or(0)
+            .max(self.next_id)
+            + 1;
+        self.next_id = id;
+        let chore = Chore {
+            id,
+            name: request.name,
+            next_due_date: request.due_date,
+            description: request.description,
+            priority: request.priority,
+            labels_v2: request.labels,
+            frequency_type: request.frequency_type,
+            frequency: request.frequency,
+            frequency_metadata: request.frequency_metadata,
+            is_rolling: request.is_rolling,
+            is_active: Some(true),
+            status: Some(0),
+            ..Chore::default()
+        };
+        self.chores.push(chore.clone());
+        Ok(chore)
+    }
+
+    fn update(&mut self, id: i64, request: UpdateChoreRequest) -> Result<Chore, ApiError> {
+        self.calls.push(Call::Update(id));
+        self.take_failure()?;
+        let chore = self.find(id)?;
+        if let Some(name) = request.name {
+            chore.name = name;
+        }
+        if let Some(due) = request.due_date {
+            chore.next_due_date = due;
+        }
+        if request.description.is_some() {
+            chore.description = request.description;
+        }
+        if request.priority.is_some() {
+            chore.priority = request.priority;
+        }
+        if request.labels.is_some() {
+            chore.labels_v2 = request.labels;
+        }
+        if request.frequency_type.is_some() {
+            chore.frequency_type = request.frequency_type;
+            chore.frequency = request.frequency;
+            chore.frequency_metadata = request.frequency_metadata;
+        }
+        if request.is_rolling.is_some() {
+            chore.is_rolling = request.is_rolling;
+        }
+        if request.assigned_to.is_some() {
+            chore.assigned_to = request.assigned_to;
+        }
+        Ok(chore.clone())
+    }
+
+    /// Records a completion. One-off chores are deactivated like they are on
+    /// the real server; recurring ones stay (the fake does not reschedule).
+    fn complete(&mut self, id: i64) -> Result<(), ApiError> {
+        self.calls.push(Call::Complete(id));
+        self.take_failure()?;
+        let chore = self.find(id)?;
+        let due = chore.next_due_date;
+        let one_off = matches!(chore.frequency_type.as_deref(), None | Some("once"));
+        let entries = self.history.entry(id).or_default();
+        entries.push(ChoreHistory {
+            id: entries.len() as i64 + 1,
+            chore_id: id,
+            performed_at: Some(Utc::now()),
+            completed_by: None,
+            assigned_to: None,
+            notes: None,
+            due_date: due,
+        });
+        if one_off {
+            self.find(id)?.is_active = Some(false);
+        }
+        Ok(())
+    }
+
+    fn delete(&mut self, id: i64) -> Result<(), ApiError> {
+        self.calls.push(Call::Delete(id));
+        self.take_failure()?;
+        self.find(id)?;
+        self.chores.retain(|chore| chore.id != id);
+        Ok(())
+    }
+
+    fn set_active(&mut self, id: i64, active: bool) -> Result<(), ApiError> {
+        self.calls.push(Call::SetActive(id, active));
+        self.take_failure()?;
+        self.find(id)?.is_active = Some(active);
+        Ok(())
+    }
+}
+
+/// An in-memory `ChoreApi`. Clones share the same store, so a test can keep
+/// one to inspect what the handlers did.
+#[derive(Debug, Clone, Default)]
+pub struct FakeApi {
+    store: Arc<Mutex<Store>>,
+}
+
+impl FakeApi {
+    pub fn with_chores(chores: Vec<Chore>) -> Self {
+        let api = Self::default();
+        api.store().chores = chores;
+        api
+    }
+
+    pub fn store(&self) -> MutexGuard<'_, Store> {
+        self.store.lock().unwrap()
+    }
+
+    pub fn calls(&self) -> Vec<Call> {
+        self.store().calls.clone()
+    }
+
+    pub fn fail_next(&self, error: ApiError) {
+        self.store().fail_next = Some(error);
+    }
+}
+
+impl ChoreApi for FakeApi {
+    async fn list_chores(&self) -> Result<Vec<Chore>, ApiError> {
+        self.store().list()
+    }
+
+    async fn chore_history(&self, id: i64) -> Result<Vec<ChoreHistory>, ApiError> {
+        self.store().history(id)
+    }
+
+    async fn create_chore(&self, request: CreateChoreRequest) -> Result<Chore, ApiError> {
+        self.store().create(request)
+    }
+
+    async fn update_chore(&self, id: i64, request: UpdateChoreRequest) -> Result<Chore, ApiError> {
+        self.store().update(id, request)
+    }
+
+    async fn complete_chore(&self, id: i64) -> Result<(), ApiError> {
+        self.store().complete(id)
+    }
+
+    async fn delete_chore(&self, id: i64) -> Result<(), ApiError> {
+        self.store().delete(id)
+    }
+
+    async fn set_chore_active(&self, id: i64, active: bool) -> Result<(), ApiError> {
+        self.store().set_active(id, active)
+    }
+}
+
+/// How list endpoints wrap their results. Donetick has shipped both.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ListFormat {
+    /// `{"res": [...]}`
+    Wrapped,
+    /// `[...]`
+    Bare,
+}
+
+/// A request as the mock server saw it.
+#[derive(Debug, Clone)]
+pub struct Request {
+    pub method: String,
+    pub path: String,
+    pub headers: HashMap<String, String>,
+    pub body: String,
+}
+
+/// A canned reply served instead of routing the next request.
+#[derive(Debug, Clone)]
+pub struct Scripted {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+#[derive(Debug)]
+struct ServerState {
+    api: FakeApi,
+    format: ListFormat,
+    scripted: VecDeque<Scripted>,
+    requests: Vec<Request>,
+}
+
+/// A local HTTP/1.1 stand-in for the `/eapi/v1/chore` endpoints, backed by a
+/// `FakeApi` store. Every response closes the connection, which keeps the
+/// request parsing trivial.
+pub struct MockServer {
+    url: String,
+    state: Arc<Mutex<ServerState>>,
+}
+
+impl MockServer {
+    pub async fn start(api: FakeApi, format: ListFormat) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let state = Arc::new(Mutex::new(ServerState {
+            api,
+            format,
+            scripted: VecDeque::new(),
+            requests: Vec::new(),
+        }));
+
+        let shared = state.clone();
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                let state = shared.clone();
+                tokio::spawn(async move {
+                    let _ = serve(stream, state).await;
+                });
+            }
+        });
+
+        Self { url, state }
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// A client for this server that retries quickly.
+    pub fn client(&self) -> ApiClient {
+        let mut settings = ClientSettings::default();
+        settings.retry.base_delay = std::time::Duration::from_millis(1);
+        ApiClient::new(self.url.clone(), TOKEN.to_string(), settings).unwrap()
+    }
+
+    /// Queues a reply for the next unanswered request.
+    pub fn respond_with(&self, status: u16, body: &str) {
+        self.respond_with_headers(status, &[], body);
+    }
+
+    pub fn respond_with_headers(&self, status: u16, headers: &[(&str, &str)], body: &str) {
+        self.state.lock().unwrap().scripted.push_back(Scripted {
+            status,
+            headers: headers
+                .iter()
+                .map(|(name, value)| (name.to_string(), value.to_string()))
+                .collect(),
+            body: body.to_string(),
+        });
+    }
+
+    pub fn requests(&self) -> Vec<Request> {
+        self.state.lock().unwrap().requests.clone()
+    }
+}
+
+async fn serve(mut stream: TcpStream, state: Arc<Mutex<ServerState>>) -> std::io::Result<()> {
+    let Some(request) = read_request(&mut stream).await? else {
+        return Ok(());
+    };
+
+    let reply = {
+        let mut state = state.lock().unwrap();
+        state.requests.push(request.clone());
+        match state.scripted.pop_front() {
+            Some(scripted) => scripted,
+            None => route(&state.api, state.format, &request),
+        }
+    };
+
+    let reason = StatusCode::from_u16(reply.status)
+        .ok()
+        .and_then(|status| status.canonical_reason())
+        .unwrap_or("Unknown");
+    let mut head = format!(
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n",
+        reply.status,
+        reason,
+        reply.body.len()
+    );
+    for (name, value) in &reply.headers {
+        head.push_str(&format!("{}: {}\r\n", name, value));
+    }
+    head.push_str("\r\n");
+
+    stream.write_all(head.as_bytes()).await?;
+    stream.write_all(reply.body.as_bytes()).await?;
+    stream.shutdown().await
+}
+
+async fn read_request(stream: &mut TcpStream) -> std::io::Result<Option<Request>> {
+    let mut buffer = Vec::new();
+    let mut chunk = [0u8; 4096];
+    let header_end = loop {
+        let read = stream.read(&mut chunk).await?;
+        if read == 0 {
+            return Ok(None);
+        }
+        buffer.extend_from_slice(&chunk[..read]);
+        if let Some(end) = buffer.windows(4).position(|w| w == b"\r\n\r\n") {
+            break end + 4;
+        }
+    };
+
+    let head = String::from_utf8_lossy(&buffer[..header_end]).to_string();
+    let mut lines = head.lines();
+    let mut request_line = lines.next().unwrap_or_default().split_whitespace();
+    let method = request_line.next().unwrap_or_default().to_string();
+    let path = request_line.next().unwrap_or_default().to_string();
+    let headers: HashMap<String, String> = lines
+        .filter_map(|line| line.split_once(':'))
+        .map(|(name, value)| (name.trim().to_ascii_lowercase(), value.trim().to_string()))
+        .collect();
+
+    let length: usize = headers
+        .get("content-length")
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(0);
+    while buffer.len() < header_end + length {
+        let read = stream.read(&mut chunk).await?;
+        if read == 0 {
+            break;
+        }
+        buffer.extend_from_slice(&chunk[..read]);
+    }
+    let body = String::from_utf8_lossy(&buffer[header_end..]).to_string();
+
+    Ok(Some(Request {
+        method,
+        path,
+        headers,
+        body,
+    }))
+}
+
+fn route(api: &FakeApi, format: ListFormat, request: &Request) -> Scripted {
+    if request.headers.get("secretkey").map(String::as_str) != Some(TOKEN) {
+        return reply(401, json!({ "error": "invalid secret key" }).to_string());
+    }
+
+    let path = request.path.trim_start_matches("/eapi/v1/chore");
+    let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+    let id = segments.first().and_then(|s| s.parse::<i64>().ok());
+    let mut store = api.store();
+
+    let result = match (request.method.as_str(), id, segments.get(1).copied()) {
+        ("GET", None, None) if segments.is_empty() => {
+            store.list().map(|chores| list_body(format, json!(chores)))
+        }
+        ("GET", Some(id), Some("history")) => store
+            .history(id)
+            .map(|entries| list_body(format, json!(entries))),
+        ("POST", None, None) if segments.is_empty() => match serde_json::from_str(&request.body) {
+            Ok(create) => store.create(create).map(|chore| json!(chore).to_string()),
+            Err(e) => return bad_request(e),
+        },
+        ("PUT", Some(id), None) => match serde_json::from_str(&request.body) {
+            Ok(update) => store
+                .update(id, update)
+                .map(|chore| json!(chore).to_string()),
+            Err(e) => return bad_request(e),
+        },
+        ("POST", Some(id), Some("complete")) => store.complete(id).map(|_| ok_body()),
+        ("DELETE", Some(id), None) => store.delete(id).map(|_| ok_body()),
+        ("PUT", Some(id), Some("archive")) => store.set_active(id, false).map(|_| ok_body()),
+        ("PUT", Some(id), Some("unarchive")) => store.set_active(id, true).map(|_| ok_body()),
+        _ => return reply(404, json!({ "error": "no such route" }).to_string()),
+    };
+
+    match result {
+        Ok(body) => reply(200, body),
+        Err(error) => {
+            let (status, message) = match error {
+                ApiError::NotFound(message) => (404, message),
+                ApiError::Conflict(message) => (409, message),
+                ApiError::Unauthorized(message) => (401, message),
+                ApiError::ServerError(status, message) => (status, message),
+                other => (500, other.to_string()),
+            };
+            reply(status, json!({ "error": message }).to_string())
+        }
+    }
+}
+
+fn list_body(format: ListFormat, items: serde_json::Value) -> String {
+    match format {
+        ListFormat::Wrapped => json!({ "res": items }).to_string(),
+        ListFormat::Bare => items.to_string(),
+    }
+}
+
+fn ok_body() -> String {
+    json!({ "message": "ok" }).to_string()
+}
+
+fn bad_request(error: serde_json::Error) -> Scripted {
+    reply(422, json!({ "error": error.to_string() }).to_string())
+}
+
+fn reply(status: u16, body: String) -> Scripted {
+    Scripted {
+        status,
+        headers: Vec::new(),
+        body,
+    }
+}
+
+/// Waits for the next event from a spawned handler task.
+pub async fn next_event(events: &mut EventHandler) -> AppEvent {
+    tokio::time::timeout(std::time::Duration::from_secs(5), events.next())
+        .await
+        .expect("timed out waiting for an event")
+        .expect("event channel closed")
+}
@@ -0,0 +1,86 @@
+use chrono::{DateTime, Duration, Local, TimeZone, Utc};
+use donetick_tui::dates::{parse_due_input_at, relative_due};
+
+/// Wednesday 2025-06-04, 10:00 local time.
+fn now() -> DateTime<Local> {
+    Local.with_ymd_and_hms(2025, 6, 4, 10, 0, 0).unwrap()
+}
+
+fn local(year: i32, month: u32, day: u32, hour: u32, minute: u32) -> Option<DateTime<Utc>> {
+    let at = Local
+        .with_ymd_and_hms(year, month, day, hour, minute, 0)
+        .unwrap();
+    Some(at.with_timezone(&Utc))
+}
+
+fn parse(input: &str) -> Option<DateTime<Utc>> {
+    parse_due_input_at(input, now())
+}
+
+#[test]
+fn day_words() {
+    assert_eq!(parse("today"), local(2025, 6, 4, 0, 0));
+    assert_eq!(parse("tomorrow"), local(2025, 6, 5, 0, 0));
+    assert_eq!(parse("Tomorrow 9am"), local(2025, 6, 5, 9, 0));
+    assert_eq!(parse("tomorrow at 5:30 pm"), local(2025, 6, 5, 17, 30));
+    assert_eq!(parse("eom"), local(2025, 6, 30, 0, 0));
+}
+
+#[test]
+fn weekdays_are_always_ahead() {
+    assert_eq!(parse("next fri"), local(2025, 6, 6, 0, 0));
+    assert_eq!(parse("fri noon"), local(2025, 6, 6, 12, 0));
+    // Today is a Wednesday, so "wed" is a week out
+    assert_eq!(parse("wed"), local(2025, 6, 11, 0, 0));
+    assert_eq!(parse("next week"), local(2025, 6, 9, 0, 0));
+}
+
+#[test]
+fn offsets() {
+    assert_eq!(parse("in 3d"), local(2025, 6, 7, 0, 0));
+    assert_eq!(parse("in 3 days"), local(2025, 6, 7, 0, 0));
+    assert_eq!(parse("in 2w"), local(2025, 6, 18, 0, 0));
+    assert_eq!(parse("in 1mo"), local(2025, 7, 4, 0, 0));
+    // Hours and minutes count from now rather than midnight
+    assert_eq!(parse("in 2h"), local(2025, 6, 4, 12, 0));
+    assert_eq!(parse("in 30m"), local(2025, 6, 4, 10, 30));
+}
+
+#[test]
+fn bare_times_mean_the_next_occurrence() {
+    assert_eq!(parse("17:00"), local(2025, 6, 4, 17, 0));
+    assert_eq!(parse("9am"), local(2025, 6, 5, 9, 0));
+}
+
+#[test]
+fn strict_formats_still_work() {
+    assert_eq!(parse("2025-07-01"), local(2025, 7, 1, 0, 0));
+    assert_eq!(parse("2025-07-01 08:15"), local(2025, 7, 1, 8, 15));
+}
+
+#[test]
+fn invalid_input_is_rejected() {
+    for input in [
+        "",
+        "someday",
+        "next blursday",
+        "in 3 fortnights",
+        "tomorrow 25:00",
+        "13pm",
+        "2025-02-31",
+        "tomorrow 9am please",
+    ] {
+        assert_eq!(parse(input), None, "{:?}", input);
+    }
+}
+
+#[test]
+fn just_missed_is_overdue() {
+    let now = now();
+    let due = |seconds: i64| (now + Duration::seconds(seconds)).with_timezone(&Utc);
+
+    assert_eq!(relative_due(due(-30), now), "overdue 1m");
+    assert_eq!(relative_due(due(0), now), "now");
+    assert_eq!(relative_due(due(30), now), "now");
+    assert_eq!(relative_due(due(-2 * 3600), now), "overdue 2h");
+}
@@ -0,0 +1,718 @@
+mod common;
+
+use common::{chore, next_event, Call, FakeApi};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+use donetick_tui::api::error::ApiError;
+use donetick_tui::api::types::Assignee;
+use donetick_tui::app::{App, FormField, View};
+use donetick_tui::bulk::{BulkAction, BulkResult};
+use donetick_tui::event::{
+    handle_key_event, handle_mouse_event, handle_paste, spawn_load_tasks, spawn_refresh, AppEvent,
+    EventHandler, EventSender,
+};
+use donetick_tui::keymap::{Action, Keymap};
+use donetick_tui::ui;
+use donetick_tui::undo::Undo;
+use ratatui::backend::TestBackend;
+use ratatui::layout::Rect;
+use ratatui::Terminal;
+
+struct Harness {
+    app: App,
+    api: FakeApi,
+    tx: EventSender,
+    events: EventHandler,
+}
+
+impl Harness {
+    fn new() -> Self {
+        let chores = vec![chore(1, "Dishes"), chore(2, "Laundry"), chore(3, "Vacuum")];
+        let api = FakeApi::with_chores(chores.clone());
+        let mut app = App::new();
+        app.set_tasks(chores);
+        let events = EventHandler::new();
+        let tx = events.sender();
+        Self {
+            app,
+            api,
+            tx,
+            events,
+        }
+    }
+
+    async fn press(&mut self, code: KeyCode) {
+        let key = KeyEvent::new(code, KeyModifiers::NONE);
+        handle_key_event(key, &mut self.app, &self.api, &self.tx).await;
+    }
+
+    async fn type_text(&mut self, text: &str) {
+        for c in text.chars() {
+            self.press(KeyCode::Char(c)).await;
+        }
+    }
+
+    /// Draws a frame so the app knows where everything is on screen.
+    fn draw(&mut self) {
+        let mut terminal = Terminal::new(TestBackend::new(80, 24)).unwrap();
+        terminal.draw(|f| ui::draw(f, &mut self.app)).unwrap();
+    }
+
+    async fn mouse(&mut self, kind: MouseEventKind, column: u16, row: u16) {
+        let mouse = MouseEvent {
+            kind,
+            column,
+            row,
+            modifiers: KeyModifiers::NONE,
+        };
+        handle_mouse_event(mouse, &mut self.app, &self.api, &self.tx).await;
+    }
+
+    async fn click(&mut self, region: Rect) {
+        self.mouse(MouseEventKind::Down(MouseButton::Left), region.x, region.y)
+            .await;
+    }
+
+    /// Where the footer hint for `action` was drawn.
+    fn hint(&self, action: Action) -> Rect {
+        let hint = self.app.regions.hints.iter().find(|(_, a)| *a == action);
+        hint.expect("hint is on screen").0
+    }
+
+    async fn event(&mut self) -> AppEvent {
+        next_event(&mut self.events).await
+    }
+
+    /// Waits for a bulk action to finish, skipping its progress events.
+    async fn bulk_finished(&mut self) -> (BulkAction, Vec<BulkResult>) {
+        loop {
+            match self.event().await {
+                AppEvent::BulkProgress(..) => {}
+                AppEvent::BulkFinished(action, results) => return (action, results),
+                _ => panic!("expected bulk results"),
+            }
+        }
+    }
+}
+
+#[tokio::test]
+async fn enter_completes_selected_task() {
+    let mut h = Harness::new();
+    h.press(KeyCode::Char('j')).await;
+
+    h.press(KeyCode::Enter).await;
+
+    assert!(h.app.loading);
+    assert!(matches!(h.event().await, AppEvent::TaskCompleted(_)));
+    assert_eq!(h.api.calls(), [Call::Complete(2)]);
+    assert!(h.api.store().history.contains_key(&2));
+}
+
+#[tokio::test]
+async fn page_and_jump_keys_move_selection() {
+    let mut h = Harness::new();
+    h.app.page_size = 2;
+
+    h.press(KeyCode::PageDown).await;
+    assert_eq!(h.app.selected_task, 2);
+    h.press(KeyCode::PageDown).await;
+    assert_eq!(h.app.selected_task, 2);
+    h.press(KeyCode::PageUp).await;
+    assert_eq!(h.app.selected_task, 0);
+
+    h.press(KeyCode::Char('G')).await;
+    assert_eq!(h.app.selected_task, 2);
+    h.press(KeyCode::Char('g')).await;
+    assert_eq!(h.app.selected_task, 0);
+    h.press(KeyCode::End).await;
+    assert_eq!(h.app.selected_task, 2);
+    h.press(KeyCode::Home).await;
+    assert_eq!(h.app.selected_task, 0);
+    assert!(h.api.calls().is_empty());
+}
+
+#[tokio::test]
+async fn space_and_range_mark_chores() {
+    let mut h = Harness::new();
+
+    h.press(KeyCode::Char(' ')).await;
+    assert_eq!(h.app.selected_task, 1);
+    h.press(KeyCode::Char('V')).await;
+    h.press(KeyCode::Char('j')).await;
+    assert!((0..3).all(|i| h.app.is_marked(i)));
+    h.press(KeyCode::Char('V')).await;
+    assert_eq!(h.app.marked.len(), 3);
+
+    h.press(KeyCode::Esc).await;
+    assert!(!h.app.has_marks());
+    assert!(h.api.calls().is_empty());
+}
+
+#[tokio::test]
+async fn enter_completes_marked_chores() {
+    let mut h = Harness::new();
+    h.press(KeyCode::Char(' ')).await;
+    h.press(KeyCode::Char('j')).await;
+    h.press(KeyCode::Char(' ')).await;
+
+    h.press(KeyCode::Enter).await;
+
+    assert!(h.app.bulk_progress.is_some());
+    let (action, results) = h.bulk_finished().await;
+    let mut calls = h.api.calls();
+    calls.sort_by_key(|c| format!("{:?}", c));
+    assert_eq!(calls, [Call::Complete(1), Call::Complete(3)]);
+
+    h.app.finish_bulk(&action, results);
+    assert_eq!(h.app.view, View::BulkSummary);
+    assert!(h.app.marked.is_empty());
+}
+
+#[tokio::test]
+async fn bulk_delete_asks_for_confirmation() {
+    let mut h = Harness::new();
+    h.press(KeyCode::Char('V')).await;
+    h.press(KeyCode::Char('G')).await;
+
+    h.press(KeyCode::Char('d')).await;
+    assert_eq!(h.app.view, View::Confirm);
+    assert!(h
+        .app
+        .confirmation
+        .as_ref()
+        .unwrap()
+        .message
+        .contains("3 chores"));
+    h.press(KeyCode::Char('y')).await;
+
+    h.bulk_finished().await;
+    assert!(h.api.store().chores.is_empty());
+}
+
+#[tokio::test]
+async fn bulk_reassign_resolves_names() {
+    let mut h = Harness::new();
+    h.app.tasks[0].assignees = Some(vec![Assignee {
+        id: 8,
+        name: "Alex".to_string(),
+    }]);
+    h.press(KeyCode::Char(' ')).await;
+
+    h.press(KeyCode::Char('A')).await;
+    assert_eq!(h.app.view, View::BulkEdit);
+    h.type_text("nobody").await;
+    h.press(KeyCode::Enter).await;
+    assert!(h.app.bulk_prompt.error.is_some());
+    assert!(h.api.calls().is_empty());
+
+    h.app.bulk_prompt.input = "alex".to_string();
+    h.press(KeyCode::Enter).await;
+    h.bulk_finished().await;
+    assert_eq!(h.api.store().chores[0].assigned_to, Some(8));
+}
+
+#[tokio::test]
+async fn undo_brings_back_completed_chore() {
+    let mut h = Harness::new();
+    h.press(KeyCode::Enter).await;
+    let AppEvent::TaskCompleted(undo) = h.event().await else {
+        panic!("expected a completion");
+    };
+    h.app.push_undo(undo);
+    assert_eq!(
+        h.app.status.as_deref(),
+        Some("Completed Dishes — press u to undo")
+    );
+    // One-off chores are deactivated on completion
+    assert_eq!(h.api.store().chores[0].is_active, Some(false));
+
+    h.press(KeyCode::Char('u')).await;
+
+    assert!(matches!(h.event().await, AppEvent::Undone(_)));
+    let store = h.api.store();
+    let restored = &store.chores[0];
+    assert_eq!(restored.is_active, Some(true));
+    assert_eq!(restored.next_due_date, h.app.tasks[0].next_due_date);
+    assert!(h.app.undo.is_empty());
+}
+
+#[tokio::test]
+async fn undo_restores_edited_fields() {
+    let mut h = Harness::new();
+    h.press(KeyCode::Char('e')).await;
+    h.type_text(" twice").await;
+    h.press(KeyCode::Enter).await;
+    let AppEvent::TaskUpdated(undo) = h.event().await else {
+        panic!("expected an update");
+    };
+    h.app.show_task_list();
+    h.app.push_undo(undo);
+    assert_eq!(h.api.store().chores[0].name, "Dishes twice");
+
+    h.press(KeyCode::Char('u')).await;
+
+    assert!(matches!(h.event().await, AppEvent::Undone(_)));
+    assert_eq!(h.api.store().chores[0].name, "Dishes");
+    assert_eq!(h.api.calls(), [Call::Update(1), Call::Update(1)]);
+}
+
+#[tokio::test]
+async fn undo_reverts_bulk_action_as_a_whole() {
+    let mut h = Harness::new();
+    h.press(KeyCode::Char('V')).await;
+    h.press(KeyCode::Char('G')).await;
+    h.press(KeyCode::Char('d')).await;
+    h.press(KeyCode::Char('y')).await;
+    let (action, results) = h.bulk_finished().await;
+    h.app.finish_bulk(&action, results);
+    assert!(h.api.store().chores.is_empty());
+
+    h.press(KeyCode::Esc).await;
+    h.press(KeyCode::Char('u')).await;
+
+    let AppEvent::Undone(undo) = h.event().await else {
+        panic!("expected the undo to finish");
+    };
+    assert_eq!(undo.subject, "3 chores");
+    let names: Vec<_> = h
+        .api
+        .store()
+        .chores
+        .iter()
+        .map(|c| c.name.clone())
+        .collect();
+    assert_eq!(names, ["Dishes", "Laundry", "Vacuum"]);
+}
+
+#[tokio::test]
+async fn failed_undo_is_handed_back() {
+    let mut h = Harness::new();
+    h.app.push_undo(Undo::deleted(&chore(4, "Mop")));
+    h.api
+        .fail_next(ApiError::ServerError(500, "database is locked".to_string()));
+
+    h.press(KeyCode::Char('u')).await;
+
+    // The main loop puts it back on the stack to try again
+    let AppEvent::UndoFailed(undo, ApiError::ServerError(..)) = h.event().await else {
+        panic!("expected the undo to fail");
+    };
+    assert_eq!(undo.subject, "Mop");
+    assert_eq!(undo.reverts.len(), 1);
+}
+
+#[tokio::test]
+async fn undo_with_nothing_to_undo() {
+    let mut h = Harness::new();
+
+    h.press(KeyCode::Char('u')).await;
+
+    assert_eq!(h.app.status.as_deref(), Some("Nothing to undo"));
+    assert!(h.api.calls().is_empty());
+}
+
+#[tokio::test]
+async fn failed_completion_reports_api_error() {
+    let mut h = Harness::new();
+    h.api
+        .fail_next(ApiError::Conflict("already completed".to_string()));
+
+    h.press(KeyCode::Enter).await;
+
+    let AppEvent::ApiError(error) = h.event().await else {
+        panic!("expected an API error");
+    };
+    assert!(matches!(error, ApiError::Conflict(_)));
+    assert_eq!(h.api.store().chores.len(), 3);
+}
+
+#[tokio::test]
+async fn delete_asks_for_confirmation() {
+    let mut h = Harness::new();
+
+    h.press(KeyCode::Char('d')).await;
+    assert_eq!(h.app.view, View::Confirm);
+    assert!(h.api.calls().is_empty());
+
+    h.press(KeyCode::Char('y')).await;
+    assert!(matches!(h.event().await, AppEvent::TaskDeleted(_)));
+    assert_eq!(h.app.view, View::TaskList);
+    assert_eq!(h.api.calls(), [Call::Delete(1)]);
+    assert_eq!(h.api.store().chores.len(), 2);
+}
+
+#[tokio::test]
+async fn declined_confirmation_does_nothing() {
+    let mut h = Harness::new();
+
+    h.press(KeyCode::Char('x')).await;
+    h.press(KeyCode::Char('n')).await;
+
+    assert_eq!(h.app.view, View::TaskList);
+    assert!(h.app.confirmation.is_none());
+    assert!(h.api.calls().is_empty());
+}
+
+#[tokio::test]
+async fn archive_deactivates_chore() {
+    let mut h = Harness::new();
+
+    h.press(KeyCode::Char('x')).await;
+    h.press(KeyCode::Enter).await;
+
+    assert!(matches!(h.event().await, AppEvent::TaskArchived(_)));
+    assert_eq!(h.api.calls(), [Call::SetActive(1, false)]);
+    assert_eq!(h.api.store().chores[0].is_active, Some(false));
+}
+
+#[tokio::test]
+async fn add_form_creates_chore() {
+    let mut h = Harness::new();
+
+    h.press(KeyCode::Char('a')).await;
+    assert_eq!(h.app.view, View::AddTask);
+    h.type_text("Water plants").await;
+    h.press(KeyCode::Enter).await;
+
+    assert!(matches!(h.event().await, AppEvent::TaskAdded(_)));
+    assert_eq!(h.api.calls(), [Call::Create("Water plants".to_string())]);
+    let created = h.api.store().chores.last().cloned().unwrap();
+    assert_eq!(created.name, "Water plants");
+    assert_eq!(created.id, 4);
+}
+
+#[tokio::test]
+async fn invalid_form_is_not_submitted() {
+    let mut h = Harness::new();
+
+    h.press(KeyCode::Char('a')).await;
+    h.press(KeyCode::Enter).await;
+
+    assert_eq!(h.app.view, View::ErrorDialog);
+    assert!(h.api.calls().is_empty());
+}
+
+#[tokio::test]
+async fn edit_form_updates_chore() {
+    let mut h = Harness::new();
+
+    h.press(KeyCode::Char('e')).await;
+    assert_eq!(h.app.view, View::EditTask);
+    h.type_text(" twice").await;
+    h.press(KeyCode::Enter).await;
+
+    assert!(matches!(h.event().await, AppEvent::TaskUpdated(_)));
+    assert_eq!(h.api.calls(), [Call::Update(1)]);
+    assert_eq!(h.api.store().chores[0].name, "Dishes twice");
+}
+
+#[tokio::test]
+async fn clearing_the_due_date_in_the_edit_form_removes_it() {
+    let mut h = Harness::new();
+
+    h.press(KeyCode::Char('e')).await;
+    h.press(KeyCode::Tab).await;
+    assert_eq!(h.app.form_state.active_field, FormField::DueDate);
+    while !h.app.form_state.due_date.is_empty() {
+        h.press(KeyCode::Backspace).await;
+    }
+    h.press(KeyCode::Enter).await;
+
+    assert!(matches!(h.event().await, AppEvent::TaskUpdated(_)));
+    assert_eq!(h.api.store().chores[0].next_due_date, None);
+    assert_eq!(h.api.store().chores[0].name, "Dishes");
+}
+
+#[tokio::test]
+async fn history_is_loaded_for_selected_task() {
+    let mut h = Harness::new();
+
+    h.press(KeyCode::Char('h')).await;
+
+    assert_eq!(h.app.view, View::History);
+    let AppEvent::HistoryLoaded(id, entries) = h.event().await else {
+        panic!("expected history");
+    };
+    assert_eq!(id, 1);
+    assert!(entries.is_empty());
+}
+
+#[tokio::test]
+async fn refresh_reloads_tasks() {
+    let mut h = Harness::new();
+    h.api.store().chores.truncate(1);
+
+    h.press(KeyCode::Char('r')).await;
+
+    let AppEvent::TasksLoaded(tasks) = h.event().await else {
+        panic!("expected tasks");
+    };
+    assert_eq!(tasks.len(), 1);
+    assert_eq!(h.api.calls(), [Call::List]);
+}
+
+#[tokio::test]
+async fn cancelling_a_form_keeps_refreshing() {
+    let mut h = Harness::new();
+    h.app.refresh_interval = Some(std::time::Duration::from_secs(60));
+
+    h.press(KeyCode::Char('a')).await;
+    h.press(KeyCode::Esc).await;
+
+    assert_eq!(h.app.view, View::TaskList);
+    assert!(!h.app.loading);
+    assert!(h.app.refresh_due(std::time::Instant::now()));
+    assert!(h.api.calls().is_empty());
+}
+
+#[tokio::test]
+async fn periodic_refresh_is_told_apart_from_reloads() {
+    let mut h = Harness::new();
+    h.api.store().chores.truncate(2);
+
+    spawn_refresh(&h.api, &h.tx);
+
+    let AppEvent::TasksRefreshed(tasks) = h.event().await else {
+        panic!("expected refreshed tasks");
+    };
+    assert_eq!(tasks.len(), 2);
+}
+
+#[tokio::test]
+async fn load_failure_is_reported() {
+    let mut h = Harness::new();
+    h.api
+        .fail_next(ApiError::Unauthorized("token expired".to_string()));
+
+    spawn_load_tasks(&h.api, &h.tx);
+
+    assert!(matches!(
+        h.event().await,
+        AppEvent::ApiError(ApiError::Unauthorized(_))
+    ));
+}
+
+#[tokio::test]
+async fn paste_types_into_active_input() {
+    let mut h = Harness::new();
+
+    h.press(KeyCode::Char('/')).await;
+    handle_paste("Laun\r\ndry", &mut h.app);
+    assert_eq!(h.app.search.query, "Laun dry");
+
+    h.press(KeyCode::Esc).await;
+    handle_paste("ignored", &mut h.app);
+    assert!(h.app.search.query.is_empty());
+
+    h.press(KeyCode::Char('a')).await;
+    handle_paste("Water plants", &mut h.app);
+    assert_eq!(h.app.form_state.name, "Water plants");
+}
+
+#[tokio::test]
+async fn search_puts_best_matches_first() {
+    let mut h = Harness::new();
+    h.app.set_tasks(vec![
+        chore(1, "Potato toast"),
+        chore(2, "Take out trash"),
+        chore(3, "Dishes"),
+    ]);
+
+    h.press(KeyCode::Char('/')).await;
+    h.type_text("tot").await;
+    let names: Vec<&str> = h.app.visible_tasks().map(|t| t.name.as_str()).collect();
+    assert_eq!(names, ["Take out trash", "Potato toast"]);
+
+    // Clearing the search goes back to the sort order
+    h.press(KeyCode::Esc).await;
+    let names: Vec<&str> = h.app.visible_tasks().map(|t| t.name.as_str()).collect();
+    assert_eq!(names, ["Potato toast", "Take out trash", "Dishes"]);
+}
+
+#[tokio::test]
+async fn shutdown_stops_background_tasks() {
+    let h = Harness::new();
+    let (done_tx, done_rx) = tokio::sync::oneshot::channel::<()>();
+    h.tx.spawn(|_| async move {
+        tokio::time::sleep(std::time::Duration::from_secs(60)).await;
+        let _ = done_tx.send(());
+    });
+
+    h.events.shutdown();
+
+    // The sleeping task is dropped, closing its end without sending
+    let result = tokio::time::timeout(std::time::Duration::from_secs(5), done_rx).await;
+    assert!(matches!(result, Ok(Err(_))));
+}
+
+#[tokio::test]
+async fn clicking_a_row_selects_it() {
+    let mut h = Harness::new();
+    h.draw();
+    let rows = h.app.regions.task_rows;
+
+    h.click(Rect {
+        y: rows.y + 2,
+        ..rows
+    })
+    .await;
+    assert_eq!(h.app.select_task().unwrap().name, "Vacuum");
+
+    // Below the last task
+    h.click(Rect {
+        y: rows.y + 5,
+        ..rows
+    })
+    .await;
+    assert_eq!(h.app.selected_task, 2);
+}
+
+#[tokio::test]
+async fn wheel_scrolls_list_and_drags_selection() {
+    let mut h = Harness::new();
+    h.app
+        .set_tasks((1..=40).map(|id| chore(id, "Chore")).collect());
+    h.draw();
+    assert_eq!(h.app.page_size, 20);
+
+    h.mouse(MouseEventKind::ScrollDown, 10, 10).await;
+    assert_eq!(h.app.scroll_offset, 3);
+    assert_eq!(h.app.selected_task, 3);
+
+    for _ in 0..10 {
+        h.mouse(MouseEventKind::ScrollDown, 10, 10).await;
+    }
+    assert_eq!(h.app.scroll_offset, 20);
+
+    h.mouse(MouseEventKind::ScrollUp, 10, 10).await;
+    assert_eq!(h.app.scroll_offset, 17);
+    assert_eq!(h.app.selected_task, 20);
+}
+
+#[tokio::test]
+async fn clicking_footer_hints_and_form_fields() {
+    let mut h = Harness::new();
+    h.draw();
+
+    h.click(h.hint(Action::Add)).await;
+    assert_eq!(h.app.view, View::AddTask);
+
+    h.draw();
+    let due = h
+        .app
+        .regions
+        .form_fields
+        .iter()
+        .find(|(field, _)| *field == FormField::DueDate)
+        .unwrap()
+        .1;
+    h.click(due).await;
+    assert_eq!(h.app.form_state.active_field, FormField::DueDate);
+
+    h.click(h.hint(Action::Cancel)).await;
+    assert_eq!(h.app.view, View::TaskList);
+}
+
+#[tokio::test]
+async fn click_dismisses_error_dialog() {
+    let mut h = Harness::new();
+    h.app.show_error("Something went wrong".to_string());
+    h.draw();
+
+    h.click(Rect::new(0, 0, 1, 1)).await;
+
+    assert_eq!(h.app.view, View::TaskList);
+    assert!(h.app.error_message.is_none());
+}
+
+#[tokio::test]
+async fn configured_sequences_run_their_action() {
+    let mut h = Harness::new();
+    let keys = [("first", "gg"), ("delete", "dd")]
+        .into_iter()
+        .map(|(action, keys)| (action.to_string(), vec![keys.to_string()]))
+        .collect();
+    h.app.keymap = Keymap::with_overrides(&keys).unwrap();
+
+    h.press(KeyCode::Char('G')).await;
+    h.press(KeyCode::Char('g')).await;
+    assert_eq!(h.app.selected_task, 2);
+    assert_eq!(h.app.pending_keys.len(), 1);
+    h.press(KeyCode::Char('g')).await;
+    assert_eq!(h.app.selected_task, 0);
+
+    // A sequence that goes nowhere falls back to the last key alone
+    h.press(KeyCode::Char('d')).await;
+    h.press(KeyCode::Char('j')).await;
+    assert_eq!(h.app.selected_task, 1);
+    assert!(h.app.pending_keys.is_empty());
+
+    h.press(KeyCode::Char('d')).await;
+    h.press(KeyCode::Char('d')).await;
+    assert_eq!(h.app.view, View::Confirm);
+}
+
+#[tokio::test]
+async fn help_screen_opens_and_closes() {
+    let mut h = Harness::new();
+
+    h.press(KeyCode::Char('?')).await;
+    assert_eq!(h.app.view, View::Help);
+    h.press(KeyCode::Char('j')).await;
+    assert_eq!(h.app.help_scroll, 1);
+
+    h.press(KeyCode::Esc).await;
+    assert_eq!(h.app.view, View::TaskList);
+}
+
+#[tokio::test]
+async fn switching_profile_during_a_bulk_action_frees_the_list() {
+    let mut h = Harness::new();
+    h.press(KeyCode::Char(' ')).await;
+    h.press(KeyCode::Enter).await;
+    h.press(KeyCode::Char('V')).await;
+    assert!(h.app.bulk_progress.is_some());
+
+    // The old profile's BulkFinished is dropped, so nothing else ends it
+    h.app.open_profile(Some("elsewhere".to_string()), None);
+
+    assert!(h.app.bulk_progress.is_none());
+    assert!(h.app.visual_anchor.is_none());
+    h.app.set_tasks(vec![chore(5, "Mop")]);
+    h.press(KeyCode::Char(' ')).await;
+    h.press(KeyCode::Enter).await;
+    assert!(h.app.bulk_progress.is_some());
+    assert_ne!(
+        h.app.status.as_deref(),
+        Some("Wait for the current bulk action to finish")
+    );
+}
+
+#[tokio::test]
+async fn unknown_profile_does_not_leave_the_list_loading() {
+    let mut h = Harness::new();
+    h.app.show_profiles(vec!["nowhere".to_string()]);
+
+    h.press(KeyCode::Enter).await;
+
+    assert!(!h.app.loading);
+    assert!(matches!(h.event().await, AppEvent::Error(_)));
+    assert_eq!(h.app.profile, None);
+}
+
+#[tokio::test]
+async fn results_for_an_earlier_profile_are_dropped() {
+    let mut events = EventHandler::new();
+    let old = events.sender().for_generation(1);
+    let current = events.sender().for_generation(2);
+    old.send(AppEvent::TasksLoaded(vec![chore(9, "Other server")]));
+    old.send(AppEvent::Retrying(1, 3));
+    // Input and ticks are not tied to a profile
+    old.send(AppEvent::Tick);
+    current.send(AppEvent::TasksLoaded(Vec::new()));
+
+    assert!(matches!(events.next_for(2).await, Some(AppEvent::Tick)));
+    match events.next_for(2).await {
+        Some(AppEvent::TasksLoaded(tasks)) => assert!(tasks.is_empty()),
+        _ => panic!("expected the current profile's list"),
+    }
+}
@@ -0,0 +1,24 @@
+mod common;
+
+use common::fixtures::{self, now};
+use donetick_tui::filter::Filter;
+
+/// Names of the shared fixture's chores that `query` keeps.
+fn kept(query: &str) -> Vec<String> {
+    let filter = Filter::parse(query, Some("alex")).unwrap();
+    fixtures::shared()
+        .into_iter()
+        .filter(|chore| filter.matches(chore, now()))
+        .map(|chore| chore.name)
+        .collect()
+}
+
+#[test]
+fn assignee_matches_everyone_sharing_a_chore() {
+    assert_eq!(kept("assignee:sam"), ["Mow the lawn"]);
+    assert_eq!(kept("assignee:Alex"), ["Mow the lawn"]);
+    assert_eq!(kept("assignee:8"), ["Mow the lawn"]);
+    assert_eq!(kept("assignee:me"), ["Mow the lawn"]);
+    assert!(kept("assignee:kim").is_empty());
+    assert_eq!(kept("-assignee:alex"), ["Defrost the freezer"]);
+}
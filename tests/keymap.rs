@@ -0,0 +1,100 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use donetick_tui::keymap::{Action, Context, KeyPress, Keymap, Lookup, Sequence};
+use std::collections::BTreeMap;
+
+fn key(c: char) -> KeyPress {
+    KeyPress::new(KeyCode::Char(c), KeyModifiers::NONE)
+}
+
+fn overrides(entries: &[(&str, &[&str])]) -> BTreeMap<String, Vec<String>> {
+    entries
+        .iter()
+        .map(|(action, keys)| {
+            let keys = keys.iter().map(|k| k.to_string()).collect();
+            (action.to_string(), keys)
+        })
+        .collect()
+}
+
+#[test]
+fn sequences_parse_and_display() {
+    assert_eq!(
+        Sequence::parse("gg").unwrap(),
+        Sequence(vec![key('g'), key('g')])
+    );
+    assert_eq!(Sequence::parse("G").unwrap(), Sequence(vec![key('G')]));
+    assert_eq!(
+        Sequence::parse("ctrl+x ctrl+s").unwrap().to_string(),
+        "Ctrl+x Ctrl+s"
+    );
+    assert_eq!(Sequence::parse("Enter").unwrap().to_string(), "Enter");
+    assert_eq!(
+        Sequence::parse("shift+g").unwrap(),
+        Sequence(vec![key('G')])
+    );
+    assert_eq!(Sequence::parse("+").unwrap(), Sequence(vec![key('+')]));
+    assert_eq!(Sequence::parse("dd").unwrap().to_string(), "dd");
+
+    assert!(Sequence::parse("hyper+x").is_err());
+    assert!(Sequence::parse("ctrl+enterr").is_err());
+    assert!(Sequence::parse("  ").is_err());
+}
+
+#[test]
+fn key_events_fold_shift_into_characters() {
+    let shifted = KeyEvent::new(KeyCode::Char('G'), KeyModifiers::SHIFT);
+    assert_eq!(KeyPress::from(shifted), key('G'));
+
+    let back_tab = KeyEvent::new(KeyCode::BackTab, KeyModifiers::SHIFT);
+    assert_eq!(
+        KeyPress::from(back_tab),
+        KeyPress::parse("shift+tab").unwrap()
+    );
+}
+
+#[test]
+fn overrides_replace_an_actions_defaults() {
+    let keymap = Keymap::with_overrides(&overrides(&[
+        ("first", &["gg", "home"]),
+        ("delete", &["dd"]),
+        ("undo", &[]),
+    ]))
+    .unwrap();
+
+    assert_eq!(keymap.label(Action::First), "gg/Home");
+    assert!(keymap.keys(Action::Undo).is_empty());
+    assert_eq!(keymap.lookup(Context::List, &[key('d')]), Lookup::Pending);
+    assert_eq!(
+        keymap.lookup(Context::List, &[key('d'), key('d')]),
+        Lookup::Action(Action::Delete)
+    );
+    assert_eq!(keymap.lookup(Context::List, &[key('u')]), Lookup::None);
+    // The same key means different things in lists and inputs
+    let enter = KeyPress::parse("enter").unwrap();
+    assert_eq!(
+        keymap.lookup(Context::List, &[enter]),
+        Lookup::Action(Action::Complete)
+    );
+    assert_eq!(
+        keymap.lookup(Context::Input, &[enter]),
+        Lookup::Action(Action::Submit)
+    );
+}
+
+#[test]
+fn unreachable_bindings_are_rejected() {
+    let unknown = Keymap::with_overrides(&overrides(&[("teleport", &["t"])]));
+    assert!(unknown.unwrap_err().contains("Unknown action \"teleport\""));
+
+    // `g` would fire before `gg` could finish
+    let shadowed = Keymap::with_overrides(&overrides(&[("last", &["gg"])]));
+    assert!(shadowed.unwrap_err().contains("clashes"));
+
+    let duplicate = Keymap::with_overrides(&overrides(&[("refresh", &["a"])]));
+    assert!(duplicate.unwrap_err().contains("clashes"));
+
+    // Plain characters are needed for typing into forms
+    let typing = Keymap::with_overrides(&overrides(&[("submit", &["s"])]));
+    assert!(typing.unwrap_err().contains("ctrl or alt"));
+    assert!(Keymap::with_overrides(&overrides(&[("submit", &["ctrl+s"])])).is_ok());
+}
@@ -0,0 +1,35 @@
+use donetick_tui::recurrence::{IntervalUnit, Recurrence};
+
+fn every(every: i32, unit: IntervalUnit) -> Result<Recurrence, String> {
+    Ok(Recurrence::Interval { every, unit })
+}
+
+#[test]
+fn intervals_parse_with_short_and_long_units() {
+    assert_eq!(Recurrence::parse("every 3 d"), every(3, IntervalUnit::Days));
+    assert_eq!(
+        Recurrence::parse("every 2 weeks"),
+        every(2, IntervalUnit::Weeks)
+    );
+    assert_eq!(
+        Recurrence::parse("every 6 mo"),
+        every(6, IntervalUnit::Months)
+    );
+    assert_eq!(
+        Recurrence::parse("Every 1 Month"),
+        every(1, IntervalUnit::Months)
+    );
+    assert_eq!(
+        Recurrence::parse("every 2 y"),
+        every(2, IntervalUnit::Years)
+    );
+}
+
+#[test]
+fn bare_m_is_not_months() {
+    // Due dates read "in 30m" as minutes, so recurrence does not guess
+    assert_eq!(
+        Recurrence::parse("every 3 m"),
+        Err("Unknown interval unit \"m\"".to_string())
+    );
+}
@@ -0,0 +1,156 @@
+mod common;
+
+use common::chore;
+use donetick_tui::api::Chore;
+use donetick_tui::app::App;
+use donetick_tui::diff::{self, Change};
+use donetick_tui::ui;
+use ratatui::backend::TestBackend;
+use ratatui::Terminal;
+use std::time::{Duration, Instant};
+
+fn app(chores: Vec<Chore>) -> App {
+    let mut app = App::new();
+    app.set_tasks(chores);
+    app
+}
+
+#[test]
+fn diff_finds_added_changed_and_removed() {
+    let old = vec![chore(1, "Dishes"), chore(2, "Laundry"), chore(3, "Vacuum")];
+    let new = vec![
+        chore(4, "Mop"),
+        Chore {
+            priority: Some(2),
+            ..chore(2, "Laundry")
+        },
+        chore(3, "Vacuum"),
+        // Queued offline, not the server's business
+        chore(-1, "Queued"),
+    ];
+
+    let changes = diff::diff(&old, &new);
+
+    assert_eq!(
+        changes,
+        [
+            (4, Change::Added),
+            (2, Change::Changed),
+            (1, Change::Removed)
+        ]
+    );
+    assert_eq!(
+        diff::summary(&changes).as_deref(),
+        Some("1 added, 1 changed, 1 removed")
+    );
+    assert_eq!(diff::summary(&[]), None);
+}
+
+#[test]
+fn reload_keeps_selected_chore() {
+    let mut app = app(vec![
+        chore(1, "Dishes"),
+        chore(2, "Laundry"),
+        chore(3, "Vacuum"),
+    ]);
+    app.next_task();
+    app.next_task();
+    assert_eq!(app.select_task().unwrap().name, "Vacuum");
+
+    // Due dates sort the list, so a new chore due soonest lands on top
+    app.refresh_tasks(vec![
+        chore(0, "Mop"),
+        chore(1, "Dishes"),
+        chore(2, "Laundry"),
+        chore(3, "Vacuum"),
+    ]);
+
+    assert_eq!(app.select_task().unwrap().name, "Vacuum");
+    assert_eq!(app.change(0), Some(Change::Added));
+    assert_eq!(app.change(3), None);
+    assert_eq!(app.status.as_deref(), Some("Updated from server: 1 added"));
+}
+
+#[test]
+fn removed_chores_are_shown_until_the_highlight_fades() {
+    let mut app = app(vec![chore(1, "Dishes"), chore(2, "Laundry")]);
+
+    app.refresh_tasks(vec![chore(2, "Laundry")]);
+
+    assert_eq!(app.highlight.removed[0].name, "Dishes");
+    assert_eq!(app.select_task().unwrap().name, "Laundry");
+    app.expire_highlight(Instant::now());
+    assert_eq!(app.highlight.removed.len(), 1);
+    app.expire_highlight(Instant::now() + Duration::from_secs(10));
+    assert!(app.highlight.removed.is_empty());
+    assert_eq!(app.change(1), None);
+}
+
+#[test]
+fn reloads_after_our_own_changes_are_not_highlighted() {
+    let mut app = app(vec![chore(1, "Dishes"), chore(2, "Laundry")]);
+
+    app.set_tasks(vec![Chore {
+        priority: Some(1),
+        ..chore(2, "Laundry")
+    }]);
+
+    assert_eq!(app.change(2), None);
+    assert!(app.highlight.removed.is_empty());
+    assert_eq!(app.status, None);
+}
+
+#[test]
+fn our_own_reload_clears_an_earlier_highlight() {
+    let mut app = app(vec![chore(1, "Dishes"), chore(2, "Laundry")]);
+    app.refresh_tasks(vec![chore(2, "Laundry")]);
+    assert_eq!(app.highlight.removed.len(), 1);
+
+    app.set_tasks(vec![chore(2, "Laundry")]);
+
+    assert!(app.highlight.removed.is_empty());
+    assert!(app.highlight.until.is_none());
+}
+
+#[test]
+fn removed_rows_do_not_scroll_with_the_list() {
+    let mut app = app((1..=40).map(|id| chore(id, "Chore")).collect());
+    let mut terminal = Terminal::new(TestBackend::new(80, 24)).unwrap();
+    terminal.draw(|f| ui::draw(f, &mut app)).unwrap();
+    let page_size = app.page_size;
+
+    app.refresh_tasks((2..=40).map(|id| chore(id, "Chore")).collect());
+    app.last_task();
+    terminal.draw(|f| ui::draw(f, &mut app)).unwrap();
+
+    // The removed row takes a line of its own below the list
+    assert_eq!(app.page_size, page_size - 1);
+    assert_eq!(app.scroll_offset, 39 - app.page_size);
+    let screen: Vec<String> = terminal
+        .backend()
+        .buffer()
+        .content()
+        .chunks(80)
+        .map(|row| row.iter().map(|cell| cell.symbol()).collect())
+        .collect();
+    assert!(screen[21].contains("removed"));
+    assert!(screen[22].contains("39 of 39"));
+}
+
+#[test]
+fn refresh_waits_for_interval_and_idle_app() {
+    let mut app = app(vec![chore(1, "Dishes")]);
+    let now = Instant::now();
+    assert!(!app.refresh_due(now), "refresh is off by default");
+
+    app.refresh_interval = Some(Duration::from_secs(60));
+    app.refreshed_at = Some(now);
+    assert!(!app.refresh_due(now + Duration::from_secs(30)));
+    assert!(app.refresh_due(now + Duration::from_secs(60)));
+
+    app.toggle_visual();
+    assert!(!app.refresh_due(now + Duration::from_secs(60)));
+    app.toggle_visual();
+    app.loading = true;
+    assert!(!app.refresh_due(now + Duration::from_secs(60)));
+}
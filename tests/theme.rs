@@ -0,0 +1,137 @@
+mod common;
+
+use common::fixtures::{self, app_with, app_with_queued, now};
+use donetick_tui::api::error::ApiError;
+use donetick_tui::app::App;
+use donetick_tui::theme::{self, StyleSpec, Theme};
+use donetick_tui::ui;
+use ratatui::backend::TestBackend;
+use ratatui::buffer::Buffer;
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::Terminal;
+use std::collections::BTreeMap;
+
+fn render(app: &mut App) -> Buffer {
+    let mut terminal = Terminal::new(TestBackend::new(120, 40)).unwrap();
+    terminal.draw(|f| ui::draw_at(f, app, now())).unwrap();
+    terminal.backend().buffer().clone()
+}
+
+fn spec(fg: Option<&str>, bg: Option<&str>, modifiers: &[&str]) -> StyleSpec {
+    StyleSpec {
+        fg: fg.map(str::to_string),
+        bg: bg.map(str::to_string),
+        modifiers: modifiers.iter().map(|m| m.to_string()).collect(),
+    }
+}
+
+/// The style of the first cell showing `text`.
+fn style_of(buffer: &Buffer, text: &str) -> Style {
+    let area = buffer.area;
+    for y in 0..area.height {
+        let line: String = (0..area.width).map(|x| buffer[(x, y)].symbol()).collect();
+        if let Some(start) = line.find(text) {
+            let x = line[..start].chars().count() as u16;
+            return buffer[(x, y)].style();
+        }
+    }
+    panic!("{:?} is not on screen", text);
+}
+
+#[test]
+fn overrides_patch_the_base_theme() {
+    let styles = BTreeMap::from([
+        ("selected".to_string(), spec(Some("black"), None, &["bold"])),
+        ("overdue".to_string(), spec(Some("#ff8800"), None, &[])),
+        ("popup".to_string(), spec(None, Some("reset"), &[])),
+    ]);
+    let theme = Theme::dark().with_overrides(&styles).unwrap();
+
+    // Unset colours keep the base theme's
+    assert_eq!(
+        theme.selected,
+        Style::default()
+            .bg(Color::DarkGray)
+            .fg(Color::Black)
+            .add_modifier(Modifier::BOLD)
+    );
+    assert_eq!(
+        theme.overdue,
+        Style::default().fg(Color::Rgb(0xff, 0x88, 0x00))
+    );
+    assert_eq!(theme.popup, Style::default().bg(Color::Reset));
+    assert_eq!(theme.marked, Theme::dark().marked);
+}
+
+#[test]
+fn bad_overrides_are_rejected() {
+    let error = |role: &str, style: StyleSpec| {
+        let styles = BTreeMap::from([(role.to_string(), style)]);
+        Theme::dark().with_overrides(&styles).unwrap_err()
+    };
+
+    assert!(error("sparkles", spec(Some("red"), None, &[])).contains("Unknown style \"sparkles\""));
+    assert!(error("overdue", spec(Some("reddish"), None, &[])).contains("\"reddish\""));
+    assert!(error("overdue", spec(None, None, &["wobbly"])).contains("Unknown modifier"));
+    assert!(Theme::NAMES
+        .iter()
+        .all(|name| Theme::built_in(name).is_some()));
+    assert_eq!(Theme::built_in("solarized"), None);
+}
+
+#[test]
+fn no_color_picks_monochrome() {
+    std::env::set_var("NO_COLOR", "1");
+    assert_eq!(theme::default_name(), "monochrome");
+    // An empty value does not count
+    std::env::set_var("NO_COLOR", "");
+    assert_eq!(theme::default_name(), "dark");
+    std::env::remove_var("NO_COLOR");
+    assert_eq!(theme::default_name(), "dark");
+}
+
+#[test]
+fn monochrome_draws_every_view_without_colour() {
+    let mut list = app_with_queued(fixtures::every_status());
+    list.toggle_mark();
+    list.notify("Refreshed".to_string());
+    let mut details = app_with(fixtures::detailed());
+    details.toggle_details();
+    let mut form = app_with(fixtures::every_status());
+    form.show_add_form();
+    form.form_state.next_field();
+    form.form_state.due_date = "sometime".to_string();
+    let mut error = app_with(fixtures::every_status());
+    error.show_api_error(&ApiError::Unauthorized("token has expired".to_string()));
+    let mut confirm = app_with(fixtures::every_status());
+    confirm.confirm_delete();
+    let mut help = app_with(fixtures::every_status());
+    help.show_help();
+
+    for mut app in [list, details, form, error, confirm, help] {
+        app.theme = Theme::monochrome();
+        let buffer = render(&mut app);
+        for cell in buffer.content() {
+            assert_eq!((cell.fg, cell.bg), (Color::Reset, Color::Reset));
+        }
+    }
+}
+
+#[test]
+fn rows_use_the_theme() {
+    let mut app = app_with(fixtures::every_status());
+    app.theme = Theme::light();
+    let buffer = render(&mut app);
+    let selected = app.visible_tasks().next().unwrap().name.clone();
+    let style = style_of(&buffer, &selected);
+    assert_eq!(
+        (style.fg, style.bg),
+        (Some(Color::Black), Some(Color::Gray))
+    );
+
+    app.theme = Theme::monochrome();
+    let buffer = render(&mut app);
+    assert!(style_of(&buffer, &selected)
+        .add_modifier
+        .contains(Modifier::REVERSED));
+}
@@ -0,0 +1,293 @@
+//! Renders every view into a `TestBackend` and compares the text against
+//! the snapshots in `tests/snapshots`. After an intentional UI change, run
+//! `INSTA_UPDATE=always cargo test --test ui_snapshots` (or
+//! `cargo insta review`) and check the diff.
+
+mod common;
+
+use common::fixtures::{self, app_with, app_with_queued, now};
+use donetick_tui::api::error::{ApiError, FieldError};
+use donetick_tui::api::Chore;
+use donetick_tui::app::{App, BulkField, View};
+use donetick_tui::bulk::{BulkAction, BulkResult};
+use donetick_tui::ui;
+use ratatui::backend::TestBackend;
+use ratatui::Terminal;
+
+/// A roomy terminal, the classic 80x24 and a cramped one.
+const SIZES: [(u16, u16); 3] = [(120, 40), (80, 24), (40, 12)];
+
+fn render(app: &mut App, width: u16, height: u16) -> TestBackend {
+    let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+    terminal.draw(|f| ui::draw_at(f, app, now())).unwrap();
+    terminal.backend().clone()
+}
+
+/// Snapshots `app` at every size in `SIZES`, each starting from the same
+/// scroll position.
+fn assert_views(name: &str, app: &mut App) {
+    let offset = app.scroll_offset;
+    for (width, height) in SIZES {
+        app.scroll_offset = offset;
+        let backend = render(app, width, height);
+        insta::assert_snapshot!(format!("{}_{}x{}", name, width, height), backend);
+    }
+}
+
+#[test]
+fn task_list_loading() {
+    assert_views("task_list_loading", &mut App::new());
+}
+
+#[test]
+fn task_list_empty() {
+    assert_views("task_list_empty", &mut app_with(Vec::new()));
+}
+
+#[test]
+fn task_list_every_status() {
+    assert_views(
+        "task_list_every_status",
+        &mut app_with(fixtures::every_status()),
+    );
+}
+
+#[test]
+fn task_list_long_names() {
+    assert_views(
+        "task_list_long_names",
+        &mut app_with(fixtures::long_names()),
+    );
+}
+
+#[test]
+fn task_list_unicode() {
+    assert_views("task_list_unicode", &mut app_with(fixtures::unicode()));
+}
+
+#[test]
+fn task_list_scrolled() {
+    let mut app = app_with(fixtures::many(40));
+    for _ in 0..25 {
+        app.next_task();
+    }
+    assert_views("task_list_scrolled", &mut app);
+}
+
+#[test]
+fn scrolling_keeps_offset_until_selection_leaves_view() {
+    let mut app = app_with(fixtures::many(40));
+    render(&mut app, 80, 24);
+    // 24 rows less the footer, borders and header
+    assert_eq!(app.page_size, 20);
+
+    app.last_task();
+    render(&mut app, 80, 24);
+    assert_eq!(app.scroll_offset, 20);
+
+    for _ in 0..5 {
+        app.previous_task();
+    }
+    render(&mut app, 80, 24);
+    assert_eq!(app.scroll_offset, 20);
+
+    app.page_up();
+    render(&mut app, 80, 24);
+    assert_eq!(app.selected_task, 14);
+    assert_eq!(app.scroll_offset, 14);
+
+    app.first_task();
+    render(&mut app, 80, 24);
+    assert_eq!(app.scroll_offset, 0);
+}
+
+#[test]
+fn task_list_refreshed() {
+    let mut app = app_with(fixtures::every_status());
+    app.next_task();
+    let mut chores = fixtures::every_status();
+    chores.retain(|c| c.name != "Descale kettle");
+    chores[1].priority = Some(1);
+    chores.push(Chore {
+        id: 8,
+        name: "Defrost the freezer".to_string(),
+        status: Some(0),
+        ..chores[0].clone()
+    });
+    app.refresh_tasks(chores);
+    assert_views("task_list_refreshed", &mut app);
+}
+
+#[test]
+fn task_list_offline_queue() {
+    let mut app = app_with_queued(fixtures::every_status());
+    app.profile = Some("home".to_string());
+    app.notify("Offline: queued create \"Buy bin bags\"".to_string());
+    assert_views("task_list_offline_queue", &mut app);
+}
+
+#[test]
+fn task_list_search_no_match() {
+    let mut app = app_with(fixtures::every_status());
+    app.start_search();
+    for c in "xyzzy".chars() {
+        app.push_search_char(c);
+    }
+    assert_views("task_list_search_no_match", &mut app);
+}
+
+#[test]
+fn task_list_filter_and_search() {
+    let mut app = app_with(fixtures::every_status());
+    for c in "-status:archived".chars() {
+        app.push_filter_char(c);
+    }
+    app.finish_filter();
+    app.start_search();
+    for c in "pl".chars() {
+        app.push_search_char(c);
+    }
+    assert_views("task_list_filter_and_search", &mut app);
+}
+
+#[test]
+fn task_list_marked() {
+    let mut app = app_with(fixtures::every_status());
+    app.toggle_mark();
+    app.next_task();
+    app.toggle_visual();
+    app.next_task();
+    app.begin_bulk(&BulkAction::Complete, 3);
+    app.bulk_progress.as_mut().unwrap().done = 1;
+    assert_views("task_list_marked", &mut app);
+}
+
+#[test]
+fn bulk_prompt() {
+    let mut app = app_with(fixtures::every_status());
+    app.toggle_mark();
+    app.toggle_mark();
+    app.start_bulk_edit(BulkField::DueDate);
+    app.bulk_prompt.input = "next fri".to_string();
+    assert_views("bulk_prompt", &mut app);
+}
+
+#[test]
+fn bulk_summary() {
+    let mut app = app_with(fixtures::every_status());
+    let results = fixtures::every_status()
+        .into_iter()
+        .take(3)
+        .map(|chore| BulkResult {
+            error: (chore.id == 2).then(|| "chore 2 not found".to_string()),
+            id: chore.id,
+            name: chore.name,
+        })
+        .collect();
+    app.finish_bulk(&BulkAction::Delete, results);
+    assert_views("bulk_summary", &mut app);
+}
+
+#[test]
+fn task_details() {
+    let mut app = app_with(fixtures::detailed());
+    app.toggle_details();
+    assert_views("task_details", &mut app);
+}
+
+#[test]
+fn add_form() {
+    let mut app = app_with(fixtures::every_status());
+    app.show_add_form();
+    for c in "Mow the lawn".chars() {
+        app.form_state.insert_char(c);
+    }
+    app.form_state.next_field();
+    for c in "tomorrow 9am".chars() {
+        app.form_state.insert_char(c);
+    }
+    assert_views("add_form", &mut app);
+}
+
+#[test]
+fn edit_form() {
+    let mut app = app_with(fixtures::detailed());
+    app.show_edit_form();
+    app.form_state.next_field();
+    app.form_state.due_date = "sometime".to_string();
+    assert_views("edit_form", &mut app);
+}
+
+#[test]
+fn error_dialog() {
+    let mut app = app_with(fixtures::every_status());
+    app.show_api_error(&ApiError::Unauthorized("token has expired".to_string()));
+    assert_views("error_dialog", &mut app);
+}
+
+#[test]
+fn error_dialog_long_message() {
+    let mut app = app_with(fixtures::every_status());
+    app.show_api_error(&ApiError::Validation {
+        message: "The chore could not be saved because some of its fields are invalid".to_string(),
+        fields: vec![
+            FieldError {
+                field: "name".to_string(),
+                message: "is required".to_string(),
+            },
+            FieldError {
+                field: "frequency".to_string(),
+                message: "must be at least 1".to_string(),
+            },
+        ],
+    });
+    assert_views("error_dialog_long_message", &mut app);
+}
+
+#[test]
+fn confirm_dialog() {
+    let mut app = app_with(fixtures::unicode());
+    app.next_task();
+    app.confirm_delete();
+    assert_eq!(app.view, View::Confirm);
+    assert_views("confirm_dialog", &mut app);
+}
+
+#[test]
+fn history() {
+    let mut app = app_with(fixtures::every_status());
+    // "Take out the trash", whose assignee names the completer
+    app.next_task();
+    app.next_task();
+    app.show_history();
+    app.history.entries = fixtures::history(1);
+    app.history.loading = false;
+    assert_views("history", &mut app);
+}
+
+#[test]
+fn history_loading() {
+    let mut app = app_with(fixtures::every_status());
+    app.show_history();
+    assert_views("history_loading", &mut app);
+}
+
+#[test]
+fn help() {
+    let mut app = app_with(fixtures::every_status());
+    app.show_help();
+    assert_views("help", &mut app);
+}
+
+#[test]
+fn profile_picker() {
+    let mut app = app_with(fixtures::every_status());
+    app.profile = Some("home".to_string());
+    app.show_profiles(vec![
+        "home".to_string(),
+        "office".to_string(),
+        "parents".to_string(),
+    ]);
+    app.profiles.next();
+    assert_views("profile_picker", &mut app);
+}
@@ -0,0 +1,112 @@
+mod common;
+
+use common::{chore, Call, FakeApi};
+use donetick_tui::api::error::ApiError;
+use donetick_tui::api::{Chore, ChoreApi};
+use donetick_tui::undo::{self, Revert, Undo, UndoStack};
+
+#[tokio::test]
+async fn recurring_completion_moves_due_date_back() {
+    let dishes = Chore {
+        frequency_type: Some("daily".to_string()),
+        frequency: Some(1),
+        ..chore(1, "Dishes")
+    };
+    let api = FakeApi::with_chores(vec![dishes.clone()]);
+    api.store().chores[0].next_due_date = Some(common::at(2));
+
+    undo::revert(&api, &Undo::completed(&dishes)).await.unwrap();
+
+    assert_eq!(api.store().chores[0].next_due_date, dishes.next_due_date);
+    assert_eq!(api.calls(), [Call::Update(1)]);
+}
+
+#[tokio::test]
+async fn archive_is_reverted_to_previous_state() {
+    let laundry = chore(2, "Laundry");
+    let api = FakeApi::with_chores(vec![laundry.clone()]);
+    api.store().chores[0].is_active = Some(false);
+
+    let undo = Undo::archived(&laundry, false);
+    assert_eq!(undo.toast(), "Archived Laundry — press u to undo");
+    undo::revert(&api, &undo).await.unwrap();
+
+    assert_eq!(api.store().chores[0].is_active, Some(true));
+}
+
+#[tokio::test]
+async fn failures_do_not_stop_other_reverts() {
+    let api = FakeApi::with_chores(vec![chore(1, "Dishes")]);
+    api.fail_next(ApiError::ServerError(500, "database is locked".to_string()));
+    let undo = Undo::bulk(
+        "Added",
+        vec![
+            Revert::Create(1),
+            Revert::Create(2),
+            Revert::Delete(chore(3, "Vacuum")),
+        ],
+    );
+
+    let (failed, error) = undo::revert(&api, &undo).await.unwrap_err();
+
+    assert!(matches!(error, ApiError::NotFound(_)));
+    let names: Vec<_> = api.store().chores.iter().map(|c| c.name.clone()).collect();
+    assert_eq!(names, ["Dishes", "Vacuum"]);
+    // Only what failed is left to try again
+    assert!(matches!(
+        failed.reverts.as_slice(),
+        [Revert::Create(1), Revert::Create(2)]
+    ));
+    assert_eq!(failed.subject, "3 chores");
+}
+
+#[tokio::test]
+async fn one_off_completion_is_reactivated() {
+    let dishes = chore(1, "Dishes");
+    let api = FakeApi::with_chores(vec![dishes.clone()]);
+    api.complete_chore(1).await.unwrap();
+    assert_eq!(api.store().chores[0].is_active, Some(false));
+
+    undo::revert(&api, &Undo::completed(&dishes)).await.unwrap();
+
+    assert_eq!(api.store().chores[0].is_active, Some(true));
+    assert_eq!(api.store().chores[0].next_due_date, dishes.next_due_date);
+    assert_eq!(
+        api.calls(),
+        [Call::Complete(1), Call::Update(1), Call::SetActive(1, true)]
+    );
+}
+
+#[tokio::test]
+async fn missing_due_dates_are_restored_as_missing() {
+    let undated = Chore {
+        frequency_type: Some("daily".to_string()),
+        next_due_date: None,
+        ..chore(1, "Water plants")
+    };
+    let api = FakeApi::with_chores(vec![undated.clone()]);
+
+    // Completing scheduled it; editing gave it a date
+    for undo in [Undo::completed(&undated), Undo::edited(&undated)] {
+        api.store().chores[0].next_due_date = Some(common::at(2));
+        undo::revert(&api, &undo).await.unwrap();
+        assert_eq!(api.store().chores[0].next_due_date, None);
+    }
+}
+
+#[test]
+fn stack_drops_oldest_entries() {
+    let mut stack = UndoStack::default();
+    for id in 1..=25 {
+        stack.push(Undo::deleted(&chore(id, &format!("Chore {}", id))));
+    }
+
+    let mut popped = Vec::new();
+    while let Some(undo) = stack.pop() {
+        popped.push(undo.subject);
+    }
+
+    assert_eq!(popped.len(), 20);
+    assert_eq!(popped.first().unwrap(), "Chore 25");
+    assert_eq!(popped.last().unwrap(), "Chore 6");
+}